@@ -0,0 +1,238 @@
+//! Collects all solutions in `src/bin` into a registry that the main binary links with the `in-process` feature,
+//! and turns the example manifests in `data/examples` into tests for each solution.
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use tinyjson::JsonValue;
+
+/// Solution modules of a directory, as `(module name, path)`.
+fn collect(dir: &Path, prefix: &str) -> Vec<(String, String)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut modules = vec![];
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if path.is_dir() && name.len() == 4 && name.parse::<u16>().is_ok() {
+            modules.extend(collect(&path, &format!("{prefix}{name}_")));
+        } else if let Some(day) = name.strip_suffix(".rs") {
+            if day.len() == 2 && day.parse::<u8>().is_ok_and(|d| (1..=25).contains(&d)) {
+                modules.push((
+                    format!("day_{prefix}{day}"),
+                    path.to_string_lossy().to_string(),
+                ));
+            }
+        }
+    }
+
+    modules
+}
+
+/// An example input with the expected answers of its parts.
+struct Example {
+    name: String,
+    /// Path of the example file, or the inline input.
+    input: Result<PathBuf, String>,
+    answers: [Option<String>; 2],
+}
+
+/// An answer in a manifest, either a string or an integer.
+fn answer(value: Option<&JsonValue>, manifest: &Path) -> Option<String> {
+    match value? {
+        JsonValue::String(answer) => Some(answer.clone()),
+        JsonValue::Number(n) if n.fract() == 0.0 && n.abs() < 2f64.powi(53) => {
+            Some(format!("{n:.0}"))
+        }
+        JsonValue::Null => None,
+        _ => panic!(
+            "{}: answers must be strings or integers below 2^53.",
+            manifest.display()
+        ),
+    }
+}
+
+/// Read the example manifest `NN.json` of a day from its examples directory.
+fn read_manifest(examples_dir: &Path, day: &str) -> Vec<Example> {
+    let manifest = examples_dir.join(format!("{day}.json"));
+    let Ok(contents) = fs::read_to_string(&manifest) else {
+        return vec![];
+    };
+
+    let fail = |message: &str| -> ! { panic!("{}: {message}", manifest.display()) };
+
+    let json: JsonValue = contents.parse().unwrap_or_else(|e| fail(&format!("{e}")));
+    let JsonValue::Array(entries) = json else {
+        fail("expected a list of examples.");
+    };
+
+    let mut names = HashMap::new();
+    entries
+        .iter()
+        .map(|entry| {
+            let entry: &HashMap<_, _> = entry
+                .get()
+                .unwrap_or_else(|| fail("examples must be objects."));
+
+            let name = match entry.get("name") {
+                Some(JsonValue::String(name)) => name.clone(),
+                _ => fail("every example needs a `name`."),
+            };
+            // test names are derived from example names, so they have to be unique as well.
+            match names.insert(test_name(&name, 1), name.clone()) {
+                Some(other) if other == name => {
+                    fail(&format!("example `{name}` is defined more than once."))
+                }
+                Some(other) => fail(&format!(
+                    "examples `{other}` and `{name}` would get the same test names, rename one of them."
+                )),
+                None => {}
+            }
+
+            let input = match (entry.get("input"), entry.get("file")) {
+                (Some(JsonValue::String(input)), None) => Err(input.clone()),
+                (None, Some(JsonValue::String(file))) => Ok(examples_dir.join(file)),
+                (None, None) => Ok(examples_dir.join(format!("{day}.txt"))),
+                _ => fail(&format!(
+                    "example `{name}` needs either an `input` or a `file`."
+                )),
+            };
+
+            Example {
+                name,
+                input,
+                answers: [
+                    answer(entry.get("part_1"), &manifest),
+                    answer(entry.get("part_2"), &manifest),
+                ],
+            }
+        })
+        .collect()
+}
+
+/// A test function name for an example, e.g. `part_1_larger_grid`.
+fn test_name(example: &str, part: usize) -> String {
+    let name: String = example
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("part_{part}_{name}")
+}
+
+/// Tests for the examples of a day, included by the `solution!` macro.
+fn example_tests(examples: &[Example]) -> String {
+    let mut out = String::new();
+
+    for example in examples {
+        let input = match &example.input {
+            Ok(path) if path.exists() => format!("include_str!({:?})", path.display().to_string()),
+            Ok(_) => String::new(),
+            Err(input) => format!("{input:?}"),
+        };
+
+        for (i, expected) in example.answers.iter().enumerate() {
+            let Some(expected) = expected else {
+                continue;
+            };
+            let part = i + 1;
+
+            let body = match &example.input {
+                Ok(path) if input.is_empty() => {
+                    format!(
+                        "panic!({:?});",
+                        format!("example file {} does not exist.", path.display())
+                    )
+                }
+                _ => format!("check({input}, {part}, {expected:?}, {:?});", example.name),
+            };
+
+            out.push_str(&format!(
+                "#[test]\nfn {}() {{\n    {body}\n}}\n\n",
+                test_name(&example.name, part)
+            ));
+        }
+    }
+
+    out
+}
+
+/// Write the example tests of every solution to `OUT_DIR/examples/<bin name>.rs`.
+fn write_example_tests(manifest_dir: &Path, out_dir: &Path) {
+    let bins = manifest_dir.join("src/bin");
+    let examples_out = out_dir.join("examples");
+    fs::create_dir_all(&examples_out).unwrap();
+
+    let mut dirs = vec![(
+        bins.clone(),
+        manifest_dir.join("data/examples"),
+        String::new(),
+    )];
+    if let Ok(entries) = fs::read_dir(&bins) {
+        for entry in entries.filter_map(Result::ok) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_dir() && name.len() == 4 && name.parse::<u16>().is_ok() {
+                let examples = manifest_dir.join("data").join(&name).join("examples");
+                dirs.push((entry.path(), examples, format!("{name}-")));
+            }
+        }
+    }
+
+    for (bin_dir, examples_dir, prefix) in dirs {
+        println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+        let Ok(entries) = fs::read_dir(&bin_dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(day) = name.strip_suffix(".rs") else {
+                continue;
+            };
+
+            let tests = example_tests(&read_manifest(&examples_dir, day));
+            fs::write(examples_out.join(format!("{prefix}{day}.rs")), tests).unwrap();
+        }
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    if env::var_os("CARGO_FEATURE_IN_PROCESS").is_some()
+        && env::var_os("CARGO_FEATURE_DHAT_HEAP").is_some()
+    {
+        panic!("the `in-process` and `dhat-heap` features can not be combined.");
+    }
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let mut modules = collect(&Path::new(&manifest_dir).join("src/bin"), "");
+    modules.sort_unstable();
+
+    let mut out = String::new();
+    for (name, path) in &modules {
+        out.push_str(&format!(
+            "#[allow(dead_code)]\n#[path = {path:?}]\nmod {name};\n"
+        ));
+    }
+    out.push_str("\npub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n");
+    for (name, _) in &modules {
+        out.push_str(&format!("    {name}::SOLUTION,\n"));
+    }
+    out.push_str("];\n");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("solutions.rs"), out).unwrap();
+
+    write_example_tests(Path::new(&manifest_dir), &out_dir);
+}
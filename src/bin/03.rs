@@ -1,5 +1,4 @@
-use std::collections::VecDeque;
-
+use advent_of_code::vm::{self, Effect, Machine};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -13,46 +12,6 @@ advent_of_code::solution!(3);
 
 type Output = u32;
 
-struct Machine {
-    instructions: VecDeque<Instruction>,
-    mul_enabled: bool,
-}
-
-impl Machine {
-    fn new(instructions: VecDeque<Instruction>) -> Self {
-        Self {
-            instructions,
-            mul_enabled: true,
-        }
-    }
-
-    fn step(&mut self) -> Option<Output> {
-        match self.instructions.pop_front() {
-            Some(Instruction::Do) => {
-                self.mul_enabled = true;
-            }
-            Some(Instruction::Dont) => {
-                self.mul_enabled = false;
-            }
-            Some(Instruction::Mul(a, b)) => {
-                if self.mul_enabled {
-                    return Some(a * b);
-                }
-            }
-            _ => (),
-        }
-        None
-    }
-
-    fn execute(mut self) -> Output {
-        let mut result = 0;
-        while !self.instructions.is_empty() {
-            result += self.step().unwrap_or(0);
-        }
-        result
-    }
-}
-
 #[derive(Debug)]
 enum Instruction {
     Do,
@@ -60,6 +19,22 @@ enum Instruction {
     Mul(Output, Output),
 }
 
+impl vm::Instruction for Instruction {
+    /// Whether `mul` instructions are enabled.
+    type State = bool;
+    type Output = Output;
+
+    fn execute(&self, mul_enabled: &mut bool) -> Effect<Output> {
+        match self {
+            Instruction::Do => *mul_enabled = true,
+            Instruction::Dont => *mul_enabled = false,
+            Instruction::Mul(a, b) if *mul_enabled => return Effect::Output(a * b),
+            Instruction::Mul(..) => (),
+        }
+        Effect::Next
+    }
+}
+
 fn instruction_parser(input: &str) -> nom::IResult<&str, Instruction> {
     let do_parser = map(tag("do()"), |_| Instruction::Do);
     let dont_parser = map(tag("don't()"), |_| Instruction::Dont);
@@ -81,10 +56,10 @@ fn instruction_parser(input: &str) -> nom::IResult<&str, Instruction> {
     alt((do_parser, mul_parser, dont_parser))(input)
 }
 
-fn parse_input(input: &str) -> VecDeque<Instruction> {
+fn parse_input(input: &str) -> Vec<Instruction> {
     let mut preceded_mul_parser = many0(map(many_till(anychar, instruction_parser), |(_, x)| x));
 
-    preceded_mul_parser(input).unwrap().1.into()
+    preceded_mul_parser(input).unwrap().1
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -103,8 +78,8 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 pub fn part_two(input: &str) -> Option<u32> {
     let instructions = parse_input(input);
-    let machine = Machine::new(instructions);
-    Some(machine.execute())
+    let mut machine = Machine::new(instructions, true);
+    Some(machine.run().into_iter().sum())
 }
 
 #[cfg(test)]
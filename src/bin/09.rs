@@ -1,3 +1,5 @@
+use advent_of_code::parse::{digits, parse_all};
+
 advent_of_code::solution!(9);
 
 #[derive(Debug)]
@@ -9,13 +11,7 @@ enum Block {
 fn parse_input(input: &str) -> Vec<Block> {
     let mut blocks = Vec::new();
     let mut id = 0;
-    for (i, c) in input.chars().enumerate() {
-        // editor keeps re-adding the newline, crashing the program
-        if c == '\n' {
-            break;
-        }
-
-        let count = c.to_digit(10).unwrap() as u8;
+    for (i, count) in parse_all(input, digits).unwrap().into_iter().enumerate() {
         if i % 2 == 0 {
             for _ in 0..count {
                 blocks.push(Block::File { id });
@@ -0,0 +1,216 @@
+/// Multisets of states, for puzzles where only the number of each state matters and populations grow exponentially.
+///
+/// A [`Counter`] keeps one count per distinct state, so evolving it costs per distinct state
+/// rather than per item. [`count_after`] answers the same question for one state recursively, with memoization.
+use std::collections::hash_map::{self, HashMap};
+use std::hash::Hash;
+
+/// A multiset, counting how often each distinct item occurs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T: Hash + Eq> {
+    counts: HashMap<T, u64>,
+}
+
+impl<T: Hash + Eq> Default for Counter<T> {
+    fn default() -> Self {
+        Self {
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Hash + Eq> Counter<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a single occurrence of `item`.
+    pub fn add(&mut self, item: T) {
+        self.add_count(item, 1);
+    }
+
+    /// Add `count` occurrences of `item`.
+    pub fn add_count(&mut self, item: T, count: u64) {
+        if count > 0 {
+            *self.counts.entry(item).or_insert(0) += count;
+        }
+    }
+
+    /// Remove up to `count` occurrences of `item`. Returns the number of occurrences removed.
+    pub fn remove_count(&mut self, item: &T, count: u64) -> u64 {
+        let Some(current) = self.counts.get_mut(item) else {
+            return 0;
+        };
+
+        let removed = count.min(*current);
+        *current -= removed;
+        if *current == 0 {
+            self.counts.remove(item);
+        }
+        removed
+    }
+
+    /// Number of occurrences of `item`.
+    pub fn get(&self, item: &T) -> u64 {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    /// Number of distinct items.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Number of items, counting every occurrence.
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// Distinct items with their counts, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, u64)> + '_ {
+        self.counts.iter().map(|(item, count)| (item, *count))
+    }
+
+    /// Distinct items with their counts, most frequent first.
+    pub fn most_common(&self) -> Vec<(&T, u64)> {
+        let mut items: Vec<_> = self.iter().collect();
+        items.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        items
+    }
+
+    /// Replace every item by the items `rule` turns it into, carrying its count over to each of them.
+    /// An item that should survive must be part of its own output.
+    pub fn evolve<I>(&self, mut rule: impl FnMut(&T) -> I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut next = Self::new();
+        for (item, count) in self.iter() {
+            for successor in rule(item) {
+                next.add_count(successor, count);
+            }
+        }
+        next
+    }
+
+    /// Apply [`Counter::evolve`] `steps` times.
+    pub fn evolve_n<I>(self, steps: usize, mut rule: impl FnMut(&T) -> I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        (0..steps).fold(self, |counter, _| counter.evolve(&mut rule))
+    }
+}
+
+impl<T: Hash + Eq> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Hash + Eq> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add(item);
+        }
+    }
+}
+
+impl<T: Hash + Eq> IntoIterator for Counter<T> {
+    type Item = (T, u64);
+    type IntoIter = hash_map::IntoIter<T, u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+/// Number of items `state` turns into after `steps` applications of `rule`.
+/// Results are memoized per `(state, steps)`, so repeated states are only expanded once.
+pub fn count_after<S, I>(state: S, steps: usize, mut rule: impl FnMut(&S) -> I) -> u64
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    fn count<S, I>(
+        state: S,
+        steps: usize,
+        rule: &mut impl FnMut(&S) -> I,
+        memo: &mut HashMap<(S, usize), u64>,
+    ) -> u64
+    where
+        S: Hash + Eq + Clone,
+        I: IntoIterator<Item = S>,
+    {
+        if steps == 0 {
+            return 1;
+        }
+        if let Some(&n) = memo.get(&(state.clone(), steps)) {
+            return n;
+        }
+
+        let n = rule(&state)
+            .into_iter()
+            .map(|next| count(next, steps - 1, rule, memo))
+            .sum();
+        memo.insert((state, steps), n);
+        n
+    }
+
+    count(state, steps, &mut rule, &mut HashMap::new())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{count_after, Counter};
+
+    /// Rule of the stones in 2024 day 11.
+    fn blink(stone: &u64) -> Vec<u64> {
+        let digits = stone.checked_ilog10().unwrap_or(0) + 1;
+        if *stone == 0 {
+            vec![1]
+        } else if digits.is_multiple_of(2) {
+            let modulus = 10u64.pow(digits / 2);
+            vec![stone / modulus, stone % modulus]
+        } else {
+            vec![stone * 2024]
+        }
+    }
+
+    #[test]
+    fn counts_items() {
+        let mut counter: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!(counter.get(&'a'), 5);
+        assert_eq!(counter.get(&'z'), 0);
+        assert_eq!(counter.len(), 5);
+        assert_eq!(counter.total(), 11);
+        assert_eq!(counter.most_common()[0], (&'a', 5));
+
+        assert_eq!(counter.remove_count(&'c', 3), 1);
+        assert_eq!(counter.len(), 4);
+    }
+
+    #[test]
+    fn evolves_counts() {
+        let stones: Counter<u64> = [125, 17].into_iter().collect();
+        assert_eq!(stones.clone().evolve_n(6, blink).total(), 22);
+        assert_eq!(stones.evolve_n(25, blink).total(), 55312);
+    }
+
+    #[test]
+    fn counts_after_steps() {
+        let total: u64 = [125, 17]
+            .into_iter()
+            .map(|s| count_after(s, 25, blink))
+            .sum();
+        assert_eq!(total, 55312);
+        assert_eq!(count_after(0, 0, blink), 1);
+    }
+}
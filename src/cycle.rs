@@ -0,0 +1,197 @@
+/// Cycle detection for simulations that repeatedly apply a state transition.
+///
+/// The hashing helpers remember every state and find the first repeat in one pass.
+/// [`brent`] only needs `Eq` and constant memory, at the cost of running the transition more often.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cycle in a sequence of states: state `start + length` equals state `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Index of the first state that is part of the cycle.
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Index of the earliest state equal to state `n`.
+    pub fn index_at(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Run `step` from `initial` until a state repeats.
+/// Returns the cycle and all states seen, so `states[cycle.index_at(n)]` is the state after `n` steps.
+pub fn find_cycle<S>(initial: S, mut step: impl FnMut(&S) -> S) -> (Cycle, Vec<S>)
+where
+    S: Hash + Eq + Clone,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let length = states.len() - start;
+            return (Cycle { start, length }, states);
+        }
+
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// Run a simulation that may end, e.g. by leaving a map, until it ends or a state repeats.
+/// Returns `None` if `step` ends the simulation by returning `None`.
+pub fn detect_loop<S>(initial: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Cycle>
+where
+    S: Hash + Eq + Clone,
+{
+    let mut seen = HashMap::new();
+    let mut state = initial;
+
+    for index in 0.. {
+        if let Some(&start) = seen.get(&state) {
+            return Some(Cycle {
+                start,
+                length: index - start,
+            });
+        }
+
+        let next = step(&state)?;
+        seen.insert(state, index);
+        state = next;
+    }
+
+    unreachable!()
+}
+
+/// The state after `n` applications of `step`, skipping over whole cycles once one is found.
+/// Suits a large `n` such as one billion when the states cycle early.
+pub fn nth_state<S>(initial: S, n: usize, mut step: impl FnMut(&S) -> S) -> S
+where
+    S: Hash + Eq + Clone,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+
+    for index in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: index - start,
+            };
+            return states.swap_remove(cycle.index_at(n));
+        }
+
+        seen.insert(state.clone(), index);
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+
+    state
+}
+
+/// Find the cycle of `step` from `initial` with Brent's algorithm, without storing states.
+pub fn brent<S>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle
+where
+    S: Eq + Clone,
+{
+    // find the cycle length by moving the hare ahead in growing powers of two.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // with the hare `length` steps ahead, both meet at the start of the cycle.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, detect_loop, find_cycle, nth_state, Cycle};
+
+    /// 0, 1, 2, 3, 4, 5, 6, 3, 4, ...
+    fn step(n: &u32) -> u32 {
+        if *n == 6 {
+            3
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let expected = Cycle {
+            start: 3,
+            length: 4,
+        };
+
+        let (cycle, states) = find_cycle(0, step);
+        assert_eq!(cycle, expected);
+        assert_eq!(states, vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(states[cycle.index_at(9)], 5);
+        assert_eq!(cycle.index_at(2), 2);
+
+        assert_eq!(brent(0, step), expected);
+        assert_eq!(
+            brent(3, step),
+            Cycle {
+                start: 0,
+                length: 4
+            }
+        );
+    }
+
+    #[test]
+    fn fast_forwards() {
+        assert_eq!(nth_state(0, 2, step), 2);
+        assert_eq!(nth_state(0, 7, step), 3);
+        assert_eq!(nth_state(0, 1_000_000_000, step), 4);
+    }
+
+    #[test]
+    fn detects_loops_in_ending_simulations() {
+        assert_eq!(detect_loop(0, |n| (*n < 10).then(|| n + 1)), None);
+        assert_eq!(
+            detect_loop(0, |n| Some(step(n))),
+            Some(Cycle {
+                start: 3,
+                length: 4
+            })
+        );
+    }
+}
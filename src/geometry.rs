@@ -0,0 +1,346 @@
+/// 2D vectors and directions for grid-based puzzles.
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A 2D vector. As a position, `x` grows to the right and `y` grows downwards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A position on a grid, e.g. of [`crate::grid::Grid`].
+pub type Point = Vec2<usize>;
+
+/// A signed distance between two positions, e.g. the step of a [`Direction`].
+pub type Offset = Vec2<isize>;
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vec2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vec2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+macro_rules! impl_distances {
+    ($($t:ty => $u:ty),*) => {$(
+        impl Vec2<$t> {
+            /// Sum of the distances along each axis.
+            pub fn manhattan(self, other: Self) -> $u {
+                self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+            }
+
+            /// Largest distance along an axis, i.e. the number of king moves between two points.
+            pub fn chebyshev(self, other: Self) -> $u {
+                self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+            }
+        }
+    )*};
+}
+
+impl_distances!(i32 => u32, i64 => u64, isize => usize, u32 => u32, u64 => u64, usize => usize);
+
+impl Point {
+    /// Offset the point by `(dx, dy)`. Returns `None` if a coordinate would become negative.
+    pub fn checked_offset(self, dx: isize, dy: isize) -> Option<Self> {
+        self.checked_add_signed(Offset::new(dx, dy))
+    }
+
+    /// Add a signed offset. Returns `None` if a coordinate would become negative.
+    pub fn checked_add_signed(self, offset: Offset) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add_signed(offset.x)?,
+            self.y.checked_add_signed(offset.y)?,
+        ))
+    }
+
+    /// The neighbouring point in a direction, if it lies within `width` x `height`.
+    pub fn checked_step(self, direction: Direction, width: usize, height: usize) -> Option<Self> {
+        self.checked_add_signed(direction.offset())
+            .filter(|p| p.x < width && p.y < height)
+    }
+
+    /// Points along a line, starting at `self` and moving by `step` while within `width` x `height`.
+    /// E.g. the antinodes of two antennas `a` and `b` lie on `a.ray(a - b, ..)` and `b.ray(b - a, ..)`.
+    pub fn ray(self, step: Offset, width: usize, height: usize) -> impl Iterator<Item = Self> {
+        std::iter::successors(Some(self), move |p| p.checked_add_signed(step))
+            .take_while(move |p| p.x < width && p.y < height)
+    }
+
+    /// Signed version of the point, e.g. to compute an [`Offset`] between points.
+    ///
+    /// # Panics
+    /// If a coordinate exceeds `isize::MAX`.
+    pub fn signed(self) -> Offset {
+        Offset::new(
+            isize::try_from(self.x).unwrap(),
+            isize::try_from(self.y).unwrap(),
+        )
+    }
+}
+
+impl Offset {
+    /// Unsigned version of the offset. Returns `None` if a coordinate is negative.
+    pub fn unsigned(self) -> Option<Point> {
+        Some(Point::new(
+            usize::try_from(self.x).ok()?,
+            usize::try_from(self.y).ok()?,
+        ))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the 8 compass directions, in clockwise order starting at the top.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The 4 orthogonal directions, clockwise starting at the top.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// All 8 directions, clockwise starting at the top.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    fn from_index(index: u8) -> Self {
+        Self::ALL[usize::from(index % 8)]
+    }
+
+    /// Parses the usual puzzle notations: `^>v<`, `UDLR` and `NESW`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'U' | 'N' => Some(Direction::Up),
+            '>' | 'R' | 'E' => Some(Direction::Right),
+            'v' | 'D' | 'S' => Some(Direction::Down),
+            '<' | 'L' | 'W' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn offset(self) -> Offset {
+        match self {
+            Direction::Up => Offset::new(0, -1),
+            Direction::UpRight => Offset::new(1, -1),
+            Direction::Right => Offset::new(1, 0),
+            Direction::DownRight => Offset::new(1, 1),
+            Direction::Down => Offset::new(0, 1),
+            Direction::DownLeft => Offset::new(-1, 1),
+            Direction::Left => Offset::new(-1, 0),
+            Direction::UpLeft => Offset::new(-1, -1),
+        }
+    }
+
+    /// Turn by 90° clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self as u8 + 2)
+    }
+
+    /// Turn by 90° counter-clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self as u8 + 6)
+    }
+
+    /// Turn by 45° clockwise.
+    #[must_use]
+    pub fn turn_right_45(self) -> Self {
+        Self::from_index(self as u8 + 1)
+    }
+
+    /// Turn by 45° counter-clockwise.
+    #[must_use]
+    pub fn turn_left_45(self) -> Self {
+        Self::from_index(self as u8 + 7)
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        Self::from_index(self as u8 + 4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as u8 % 2 == 1
+    }
+
+    /// The direction as a single bit, see [`Directions`].
+    pub fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// A set of directions stored as a bitmask, e.g. to remember in which directions a tile was visited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Directions(pub u8);
+
+impl Directions {
+    /// Adds a direction. Returns whether it was not present yet.
+    pub fn insert(&mut self, direction: Direction) -> bool {
+        let is_new = !self.contains(direction);
+        self.0 |= direction.bit();
+        is_new
+    }
+
+    pub fn contains(self, direction: Direction) -> bool {
+        self.0 & direction.bit() != 0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = Direction> {
+        Direction::ALL
+            .into_iter()
+            .filter(move |d| self.contains(*d))
+    }
+}
+
+impl FromIterator<Direction> for Directions {
+    fn from_iter<I: IntoIterator<Item = Direction>>(iter: I) -> Self {
+        Self(iter.into_iter().fold(0, |acc, d| acc | d.bit()))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Directions, Offset, Point, Vec2};
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Vec2::new(3_i64, 4);
+        let b = Vec2::new(1_i64, 7);
+        assert_eq!(a + b, Vec2::new(4, 11));
+        assert_eq!(a - b, Vec2::new(2, -3));
+        assert_eq!(a * 2, Vec2::new(6, 8));
+        assert_eq!(-a, Vec2::new(-3, -4));
+        assert_eq!(a.manhattan(b), 5);
+        assert_eq!(a.chebyshev(b), 3);
+    }
+
+    #[test]
+    fn mixes_signed_and_unsigned() {
+        let p = Point::new(2, 1);
+        assert_eq!(
+            p.checked_add_signed(Offset::new(-2, 1)),
+            Some(Point::new(0, 2))
+        );
+        assert_eq!(p.checked_add_signed(Offset::new(0, -2)), None);
+        assert_eq!(p.signed() - Point::new(3, 3).signed(), Offset::new(-1, -2));
+        assert_eq!(Offset::new(1, -1).unsigned(), None);
+    }
+
+    #[test]
+    fn steps_within_bounds() {
+        let p = Point::new(0, 1);
+        assert_eq!(p.checked_step(Direction::Left, 3, 3), None);
+        assert_eq!(p.checked_step(Direction::Down, 3, 2), None);
+        assert_eq!(
+            p.checked_step(Direction::UpRight, 3, 3),
+            Some(Point::new(1, 0))
+        );
+    }
+
+    #[test]
+    fn iterates_rays() {
+        let a = Point::new(4, 3);
+        let b = Point::new(5, 5);
+        let ray: Vec<Point> = a.ray(a.signed() - b.signed(), 10, 10).collect();
+        assert_eq!(ray, vec![Point::new(4, 3), Point::new(3, 1)]);
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        assert_eq!(Direction::UpLeft.turn_right_45(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left_45(), Direction::UpLeft);
+        assert!(Direction::DownLeft.is_diagonal());
+    }
+
+    #[test]
+    fn stores_direction_sets() {
+        let mut visited = Directions::default();
+        assert!(visited.insert(Direction::Up));
+        assert!(!visited.insert(Direction::Up));
+        assert!(visited.insert(Direction::Left));
+        assert_eq!(
+            visited.iter().collect::<Vec<_>>(),
+            vec![Direction::Up, Direction::Left]
+        );
+        assert_eq!(
+            visited,
+            [Direction::Left, Direction::Up].into_iter().collect()
+        );
+    }
+}
@@ -0,0 +1,433 @@
+/// A dense 2D grid of cells, as found in most grid-based puzzles.
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{Direction, Point};
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    /// A line (1-based) does not have as many cells as the first line.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid has no cells"),
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line} has {found} cells, expected {expected} as in line 1"
+            ),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order.
+    ///
+    /// # Panics
+    /// If the number of cells does not match the dimensions.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "cells do not match dimensions");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses puzzle text with one row per line, mapping every character to a cell.
+    /// Blank lines at the end of the input are ignored.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        let lines: Vec<&str> = input.trim_end().lines().map(str::trim_end).collect();
+        let width = lines.first().map_or(0, |l| l.chars().count());
+        if width == 0 {
+            return Err(GridError::Empty);
+        }
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            let len = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            if cells.len() - len != width {
+                return Err(GridError::Ragged {
+                    line: i + 1,
+                    expected: width,
+                    found: cells.len() - len,
+                });
+            }
+        }
+
+        Ok(Self::new(width, lines.len(), cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    /// The cell at `point`, or `None` if it is out of bounds.
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point)
+            .then(|| &self.cells[point.y * self.width + point.x])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if self.contains(point) {
+            Some(&mut self.cells[point.y * self.width + point.x])
+        } else {
+            None
+        }
+    }
+
+    /// All points of the grid in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// All cells with their points in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The neighbouring point in a direction, if it is within bounds.
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        point.checked_step(direction, self.width, self.height)
+    }
+
+    /// Orthogonal neighbours of `point` that are within bounds, clockwise starting at the top.
+    pub fn neighbours_4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |d| self.step(point, d))
+    }
+
+    /// Orthogonal and diagonal neighbours of `point` that are within bounds, clockwise starting at the top.
+    pub fn neighbours_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(point, d))
+    }
+
+    /// Cells of row `y`, from left to right.
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            y < self.height,
+            "row {y} is out of bounds of {}x{} grid",
+            self.width,
+            self.height
+        );
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    /// Cells of column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} is out of bounds of {}x{} grid",
+            self.width,
+            self.height
+        );
+        // a grid without rows has no cells to start a column at.
+        self.cells
+            .get(x..)
+            .unwrap_or_default()
+            .iter()
+            .step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Diagonals running from top-left to bottom-right, starting at the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.diagonal_count()).map(move |i| {
+            // start on the left edge going up, then on the top edge going right.
+            let (x, y) = if i < self.height {
+                (0, self.height - 1 - i)
+            } else {
+                (i + 1 - self.height, 0)
+            };
+            (0..)
+                .map(move |d| Point::new(x + d, y + d))
+                .take_while(|p| self.contains(*p))
+                .map(|p| &self[p])
+        })
+    }
+
+    /// Diagonals running from top-right to bottom-left, starting at the top-left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.diagonal_count()).map(move |i| {
+            // start on the top edge going right, then on the right edge going down.
+            let (x, y) = if i < self.width {
+                (i, 0)
+            } else {
+                (self.width - 1, i + 1 - self.width)
+            };
+            (0..)
+                .map_while(move |d| Some(Point::new(x.checked_sub(d)?, y + d)))
+                .take_while(|p| self.contains(*p))
+                .map(|p| &self[p])
+        })
+    }
+
+    /// Number of diagonals in either direction, none if the grid has no cells.
+    fn diagonal_count(&self) -> usize {
+        if self.cells.is_empty() {
+            0
+        } else {
+            self.width + self.height - 1
+        }
+    }
+
+    /// Points of all cells equal to `value`, in row-major order.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(point, _)| point)
+    }
+
+    /// Point of the first cell equal to `value`, in row-major order.
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    /// Creates a grid of the same dimensions by mapping every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Mirrors the grid along its main diagonal, turning rows into columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Rotates the grid by 90° clockwise.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| (0..self.height).rev().map(move |y| Point::new(x, y)))
+            .map(|p| self[p].clone())
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Rotates the grid by 90° counter-clockwise.
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| (0..self.height).map(move |y| Point::new(x, y)))
+            .map(|p| self[p].clone())
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Renders the grid with one line per row, mapping every cell to a character.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.map(&mut f).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point).unwrap_or_else(|| {
+            panic!(
+                "point ({}, {}) is out of bounds of {}x{} grid",
+                point.x, point.y, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point).unwrap_or_else(|| {
+            panic!(
+                "point ({}, {}) is out of bounds of {width}x{height} grid",
+                point.x, point.y
+            )
+        })
+    }
+}
+
+/// Prints one line per row, e.g. to return a grid as an answer or to debug a simulation.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, GridError};
+    use crate::geometry::Point;
+
+    fn get_grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", |c| c).unwrap()
+    }
+
+    fn collect<'a>(lines: impl Iterator<Item = impl Iterator<Item = &'a char>>) -> Vec<String> {
+        lines.map(|l| l.collect()).collect()
+    }
+
+    #[test]
+    fn parses_with_mapping() {
+        let grid = Grid::parse("12\n34\n\n", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid[Point::new(1, 0)], 2);
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+    }
+
+    #[test]
+    fn rejects_ragged_input() {
+        assert_eq!(
+            Grid::parse("ab\nc", |c| c),
+            Err(GridError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(Grid::parse("\n", |c| c), Err(GridError::Empty));
+    }
+
+    #[test]
+    fn iterates_neighbours_within_bounds() {
+        let grid = get_grid();
+        let n4: Vec<Point> = grid.neighbours_4(Point::new(0, 0)).collect();
+        assert_eq!(n4, vec![Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbours_8(Point::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = get_grid();
+        assert_eq!(collect(grid.rows()), vec!["abc", "def"]);
+        assert_eq!(collect(grid.columns()), vec!["ad", "be", "cf"]);
+        assert_eq!(collect(grid.diagonals()), vec!["d", "ae", "bf", "c"]);
+        assert_eq!(collect(grid.anti_diagonals()), vec!["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is out of bounds of 3x2 grid")]
+    fn rejects_column_out_of_bounds() {
+        get_grid().column(3).for_each(drop);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 is out of bounds of 3x2 grid")]
+    fn rejects_row_out_of_bounds() {
+        get_grid().row(2).for_each(drop);
+    }
+
+    #[test]
+    fn iterates_empty_columns_of_grid_without_rows() {
+        let grid: Grid<char> = Grid::new(2, 0, vec![]);
+        assert_eq!(collect(grid.columns()), vec!["", ""]);
+        assert_eq!(grid.transpose(), Grid::new(0, 2, vec![]));
+    }
+
+    #[test]
+    fn iterates_no_lines_of_empty_grid() {
+        for grid in [Grid::filled(0, 0, 'a'), Grid::new(0, 2, vec![])] {
+            assert_eq!(grid.diagonals().count(), 0);
+            assert_eq!(grid.anti_diagonals().count(), 0);
+            assert_eq!(grid.columns().count(), 0);
+        }
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        let grid = get_grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+    }
+
+    #[test]
+    fn finds_values() {
+        let grid = Grid::parse("#.#\n.#.", |c| c).unwrap();
+        assert_eq!(grid.find(&'.'), Some(Point::new(1, 0)));
+        assert_eq!(grid.find_all(&'#').count(), 3);
+        assert_eq!(
+            grid.render(|c| if *c == '#' { 'X' } else { ' ' }),
+            "X X\n X "
+        );
+    }
+}
@@ -1,40 +1,65 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, read, scaffold, solve, time, watch};
 use args::{parse, AppArguments};
 
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
+use advent_of_code::template::{Day, Year};
+
+/// All solutions in `src/bin`, linked into this binary so they can run in-process.
+#[cfg(all(feature = "in-process", not(test)))]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{Day, Year};
     use std::process;
 
+    /// Slowdown in percent above which `time` reports a part as regressed.
+    const DEFAULT_REGRESSION_THRESHOLD: f64 = 10.0;
+
     pub enum AppArguments {
         Download {
+            year: Option<Year>,
             day: Day,
         },
         Read {
+            year: Option<Year>,
             day: Day,
         },
         Scaffold {
+            year: Option<Year>,
             day: Day,
             download: bool,
             overwrite: bool,
+            dry_run: bool,
+            template: Option<String>,
         },
         Solve {
+            year: Option<Year>,
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
         },
+        Watch {
+            year: Option<Year>,
+            day: Day,
+            release: bool,
+        },
         All {
+            year: Option<Year>,
             release: bool,
+            parallel: bool,
         },
         Time {
+            year: Option<Year>,
             all: bool,
             day: Option<Day>,
             store: bool,
+            stats: bool,
+            threshold: f64,
+            fail_on_regression: bool,
+            history: bool,
         },
         #[cfg(feature = "today")]
         Today,
@@ -44,31 +69,55 @@ mod args {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
+            Some("watch") => AppArguments::Watch {
+                year: args.opt_value_from_str("--year")?,
+                day: args.free_from_str()?,
+                release: args.contains("--release"),
+            },
             Some("all") => AppArguments::All {
+                year: args.opt_value_from_str("--year")?,
                 release: args.contains("--release"),
+                parallel: args.contains("--parallel"),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let stats = args.contains("--stats");
+                let fail_on_regression = args.contains("--fail-on-regression");
+                let history = args.contains("--history");
+                let threshold = args
+                    .opt_value_from_str("--threshold")?
+                    .unwrap_or(DEFAULT_REGRESSION_THRESHOLD);
 
                 AppArguments::Time {
+                    year: args.opt_value_from_str("--year")?,
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    stats,
+                    threshold,
+                    fail_on_regression,
+                    history,
                 }
             }
             Some("download") => AppArguments::Download {
+                year: args.opt_value_from_str("--year")?,
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                year: args.opt_value_from_str("--year")?,
                 day: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
+                year: args.opt_value_from_str("--year")?,
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                dry_run: args.contains("--dry-run"),
+                template: args.opt_value_from_str("--template")?,
             },
             Some("solve") => AppArguments::Solve {
+                year: args.opt_value_from_str("--year")?,
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
@@ -95,40 +144,87 @@ mod args {
     }
 }
 
+/// Download a day before scaffolding it. A failed download, e.g. before the puzzle unlocks,
+/// still lets the day be scaffolded without its puzzle description.
+fn download_for_scaffold(year: Option<Year>, day: Day) -> bool {
+    match download::try_download(year, day) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("failed to download puzzle: {e}");
+            eprintln!("Scaffolding without the puzzle description.");
+            false
+        }
+    }
+}
+
 fn main() {
+    #[cfg(all(feature = "in-process", not(test)))]
+    advent_of_code::template::registry::register(solutions::SOLUTIONS);
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                year,
+                release,
+                parallel,
+            } => all::handle(year, release, parallel),
+            AppArguments::Time {
+                year, day, history, ..
+            } if history => time::handle_history(year, day),
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                store,
+                stats,
+                threshold,
+                fail_on_regression,
+                history: _,
+            } => time::handle(year, day, all, store, stats, threshold, fail_on_regression),
+            AppArguments::Download { year, day } => download::handle(year, day),
+            AppArguments::Read { year, day } => read::handle(year, day),
             AppArguments::Scaffold {
+                year,
                 day,
                 download,
                 overwrite,
+                dry_run,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
+                // download first, so that scaffolding can use the puzzle description.
+                let downloaded = if download && dry_run {
+                    println!("Would download input and puzzle description.");
+                    true
+                } else {
+                    !download || download_for_scaffold(year, day)
+                };
+                scaffold::handle(year, day, overwrite, dry_run, template.as_deref());
+                if !downloaded {
+                    process::exit(1);
                 }
             }
             AppArguments::Solve {
+                year,
                 day,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+            } => solve::handle(year, day, release, dhat, submit),
+            AppArguments::Watch { year, day, release } => watch::handle(year, day, release),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        let downloaded = download_for_scaffold(None, day);
+                        scaffold::handle(None, day, false, false, None);
+                        if !downloaded {
+                            process::exit(1);
+                        }
+                        read::handle(None, day)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,225 @@
+/// Parsers for common puzzle input shapes, built on `nom`.
+///
+/// Run a parser with [`parse_all`]: it ignores trailing whitespace (e.g. the final newline of an input)
+/// and turns failures into a [`ParseError`] pointing at the offending line and column.
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending, satisfy, space0, space1},
+    combinator::{map_res, opt, recognize},
+    multi::{many1, separated_list1},
+    sequence::{pair, separated_pair, terminated},
+    IResult,
+};
+
+/// A parser failure, located in the parsed input.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the failure.
+    pub line: usize,
+    /// 1-based column of the failure, counted in characters.
+    pub column: usize,
+    /// The line the failure occurred in.
+    pub text: String,
+    pub message: String,
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}: `{}`",
+            self.message, self.line, self.column, self.text
+        )
+    }
+}
+
+impl ParseError {
+    /// Locate the start of `remaining`, which must be a suffix of `input`.
+    fn at(input: &str, remaining: &str, message: String) -> Self {
+        let offset = input.len() - remaining.len();
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find(['\r', '\n'])
+            .map_or(input.len(), |i| offset + i);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: input[line_start..line_end].into(),
+            message,
+        }
+    }
+}
+
+/// Run `parser` on `input` without trailing whitespace. The parser must consume all of it.
+pub fn parse_all<'a, O>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<O, ParseError> {
+    let input = input.trim_end();
+
+    match parser(input) {
+        Ok(("", output)) => Ok(output),
+        Ok((remaining, _)) => {
+            // point at the token the parser stopped at, not at the separator before it.
+            let remaining = remaining.trim_start();
+            let token = remaining.split_whitespace().next().unwrap_or_default();
+            Err(ParseError::at(
+                input,
+                remaining,
+                format!("unexpected `{token}`"),
+            ))
+        }
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(ParseError::at(
+            input,
+            e.input,
+            format!("expected {}", e.code.description()),
+        )),
+        Err(nom::Err::Incomplete(_)) => {
+            Err(ParseError::at(input, "", "unexpected end of input".into()))
+        }
+    }
+}
+
+/// A signed or unsigned integer, e.g. `42` or `-7`.
+pub fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(char('-')), digit1)), str::parse)(input)
+}
+
+/// Single-digit numbers without separators, e.g. `2333133` as in a disk map.
+pub fn digits(input: &str) -> IResult<&str, Vec<u8>> {
+    many1(map_res(satisfy(|c| c.is_ascii_digit()), |c| {
+        u8::try_from(c.to_digit(10).unwrap())
+    }))(input)
+}
+
+/// Numbers on one line separated by spaces or tabs, e.g. `7 6 4 2 1`.
+pub fn numbers<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(space1, number)(input)
+}
+
+/// Numbers separated by `separator`, e.g. `75,47,61` with `","`.
+pub fn separated_numbers<'a, T: FromStr>(
+    separator: &'a str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    separated_list1(tag(separator), number)
+}
+
+/// Two numbers separated by `separator`, e.g. `47|53` with `"|"`. Spaces around the separator are allowed.
+pub fn number_pair<'a, T: FromStr>(
+    separator: &'a str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (T, T)> {
+    separated_pair(
+        number,
+        terminated(pair(space0, tag(separator)), space0),
+        number,
+    )
+}
+
+/// A number, a colon and a list of numbers, e.g. `190: 10 19`.
+pub fn labeled_numbers<T: FromStr>(input: &str) -> IResult<&str, (T, Vec<T>)> {
+    separated_pair(number, pair(char(':'), space0), numbers)(input)
+}
+
+/// One item per line, parsed with `parser`.
+pub fn lines<'a, O>(
+    parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(line_ending, parser)
+}
+
+/// Split the input into sections separated by blank lines. Works with `\n` and `\r\n` line endings.
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut sections = vec![];
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\r', '\n']);
+        if content.trim().is_empty() {
+            if let Some(s) = start.take() {
+                sections.push(&input[s..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + content.len();
+        }
+        offset += line.len();
+    }
+
+    if let Some(s) = start {
+        sections.push(&input[s..end]);
+    }
+
+    sections
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        digits, labeled_numbers, lines, number_pair, numbers, parse_all, sections,
+        separated_numbers, ParseError,
+    };
+
+    #[test]
+    fn parses_lines_of_numbers() {
+        let input = "3   4\n4   3\n";
+        let parsed: Vec<Vec<u32>> = parse_all(input, lines(numbers)).unwrap();
+        assert_eq!(parsed, vec![vec![3, 4], vec![4, 3]]);
+
+        let parsed: Vec<i64> = parse_all("-1 2\t-3", numbers).unwrap();
+        assert_eq!(parsed, vec![-1, 2, -3]);
+    }
+
+    #[test]
+    fn parses_common_shapes() {
+        let rules: Vec<(u32, u32)> = parse_all("47|53\n97 | 13", lines(number_pair("|"))).unwrap();
+        assert_eq!(rules, vec![(47, 53), (97, 13)]);
+
+        let update: Vec<u32> = parse_all("75,47,61", separated_numbers(",")).unwrap();
+        assert_eq!(update, vec![75, 47, 61]);
+
+        let equations: Vec<(u64, Vec<u64>)> =
+            parse_all("190: 10 19\r\n3267: 81 40 27\r\n", lines(labeled_numbers)).unwrap();
+        assert_eq!(equations[1], (3267, vec![81, 40, 27]));
+
+        assert_eq!(parse_all("2333\n", digits).unwrap(), vec![2, 3, 3, 3]);
+    }
+
+    #[test]
+    fn reports_error_location() {
+        let result: Result<Vec<Vec<u32>>, _> = parse_all("1 2\n3 x 4\n", lines(numbers));
+        assert_eq!(
+            result,
+            Err(ParseError {
+                line: 2,
+                column: 3,
+                text: "3 x 4".into(),
+                message: "unexpected `x`".into(),
+            })
+        );
+
+        let result: Result<Vec<u32>, _> = parse_all("a", numbers);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "expected Digit at line 1, column 1: `a`"
+        );
+    }
+
+    #[test]
+    fn splits_sections() {
+        assert_eq!(sections("a\nb\n\nc\n"), vec!["a\nb", "c"]);
+        assert_eq!(sections("a\r\n\r\n\r\nb\r\n"), vec!["a", "b"]);
+        assert_eq!(sections("\n\n"), Vec::<&str>::new());
+    }
+}
@@ -0,0 +1,300 @@
+/// Graph searches driven by a successor function, for grid points as well as arbitrary states.
+///
+/// `successors` returns the states reachable in one step from a state. Weighted searches expect
+/// `(state, cost)` pairs instead. States only need to be hashable, costs are any ordered number.
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// All states reachable from `start`, including `start` itself.
+pub fn reachable<S, I>(start: S, mut successors: impl FnMut(&S) -> I) -> HashSet<S>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut stack = vec![start];
+
+    while let Some(state) = stack.pop() {
+        for next in successors(&state) {
+            if seen.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+    }
+
+    seen
+}
+
+/// Number of distinct paths from `start` to any goal state.
+/// Counts are memoized per state, so the successor graph must not contain cycles.
+pub fn count_paths<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> usize
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    fn count<S, I>(
+        state: &S,
+        successors: &mut impl FnMut(&S) -> I,
+        is_goal: &mut impl FnMut(&S) -> bool,
+        cache: &mut HashMap<S, usize>,
+    ) -> usize
+    where
+        S: Hash + Eq + Clone,
+        I: IntoIterator<Item = S>,
+    {
+        if is_goal(state) {
+            return 1;
+        }
+        if let Some(&n) = cache.get(state) {
+            return n;
+        }
+
+        let n = successors(state)
+            .into_iter()
+            .map(|next| count(&next, successors, is_goal, cache))
+            .sum();
+        cache.insert(state.clone(), n);
+        n
+    }
+
+    count(&start, &mut successors, &mut is_goal, &mut HashMap::new())
+}
+
+/// Number of steps to every state reachable from `start`.
+pub fn bfs_distances<S, I>(start: S, mut successors: impl FnMut(&S) -> I) -> HashMap<S, usize>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        let distance = distances[&state] + 1;
+        for next in successors(&state) {
+            distances.entry(next.clone()).or_insert_with(|| {
+                queue.push_back(next);
+                distance
+            });
+        }
+    }
+
+    distances
+}
+
+/// A shortest path, counted in steps, from `start` to the first goal state found.
+/// The path includes both `start` and the goal.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Vec<S>>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut parents: HashMap<S, S> = HashMap::new();
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            return Some(reconstruct_path(&parents, state));
+        }
+        for next in successors(&state) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), state.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// A cheapest path from `start` to the first goal state found, with its total cost.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Lowest cost to every state reachable from `start`.
+pub fn dijkstra_all<S, C, I>(start: S, mut successors: impl FnMut(&S) -> I) -> HashMap<S, C>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut costs = HashMap::from([(start.clone(), C::default())]);
+    let mut states = vec![start];
+    let mut heap = BinaryHeap::from([Reverse((C::default(), 0))]);
+
+    while let Some(Reverse((cost, index))) = heap.pop() {
+        let state = states[index].clone();
+        if costs.get(&state).is_some_and(|&c| c < cost) {
+            continue;
+        }
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|&c| next_cost < c) {
+                costs.insert(next.clone(), next_cost);
+                heap.push(Reverse((next_cost, states.len())));
+                states.push(next);
+            }
+        }
+    }
+
+    costs
+}
+
+/// A cheapest path from `start` to the first goal state found, with its total cost.
+/// `heuristic` estimates the remaining cost to a goal and must never overestimate it,
+/// e.g. the Manhattan distance on a grid.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut costs = HashMap::from([(start.clone(), C::default())]);
+    let mut parents: HashMap<S, S> = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut states = vec![start];
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let state = states[index].clone();
+        if is_goal(&state) {
+            return Some((reconstruct_path(&parents, state), cost));
+        }
+        if costs.get(&state).is_some_and(|&c| c < cost) {
+            continue;
+        }
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|&c| next_cost < c) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), state.clone());
+                heap.push(Reverse((
+                    next_cost + heuristic(&next),
+                    next_cost,
+                    states.len(),
+                )));
+                states.push(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Follow `parents` back from `goal` to the state without a parent, returning the path in order.
+pub fn reconstruct_path<S: Hash + Eq + Clone>(parents: &HashMap<S, S>, goal: S) -> Vec<S> {
+    let mut path = vec![goal];
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, bfs_distances, count_paths, dijkstra, dijkstra_all, reachable};
+    use crate::{geometry::Point, grid::Grid};
+
+    fn get_grid() -> Grid<char> {
+        Grid::parse("..#.\n.##.\n....", |c| c).unwrap()
+    }
+
+    fn open_neighbours(grid: &Grid<char>, p: Point) -> Vec<Point> {
+        grid.neighbours_4(p).filter(|n| grid[*n] == '.').collect()
+    }
+
+    #[test]
+    fn finds_reachable_points() {
+        let grid = Grid::parse("..#.\n..#.", |c| c).unwrap();
+        let seen = reachable(Point::new(0, 0), |p| open_neighbours(&grid, *p));
+        assert_eq!(seen.len(), 4);
+        assert!(!seen.contains(&Point::new(3, 0)));
+    }
+
+    #[test]
+    fn counts_paths() {
+        // number of monotone lattice paths through a 3x3 grid.
+        let paths = count_paths(
+            (0, 0),
+            |&(x, y)| {
+                [(x + 1, y), (x, y + 1)]
+                    .into_iter()
+                    .filter(|&(x, y)| x <= 2 && y <= 2)
+            },
+            |&s| s == (2, 2),
+        );
+        assert_eq!(paths, 6);
+    }
+
+    #[test]
+    fn finds_shortest_paths_on_grids() {
+        let grid = get_grid();
+        let start = Point::new(0, 0);
+        let goal = Point::new(3, 0);
+
+        let path = bfs(start, |p| open_neighbours(&grid, *p), |p| *p == goal).unwrap();
+        assert_eq!(path.len(), 8);
+        assert_eq!((path[0], path[7]), (start, goal));
+
+        let distances = bfs_distances(start, |p| open_neighbours(&grid, *p));
+        assert_eq!(distances[&goal], 7);
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        // going through `b` is longer, but cheaper.
+        let edges = |s: &char| match s {
+            'a' => vec![('b', 1), ('c', 10)],
+            'b' => vec![('c', 2)],
+            _ => vec![],
+        };
+
+        let (path, cost) = dijkstra('a', edges, |s| *s == 'c').unwrap();
+        assert_eq!((path, cost), (vec!['a', 'b', 'c'], 3));
+        assert_eq!(dijkstra_all('a', edges)[&'c'], 3);
+        assert_eq!(dijkstra('c', edges, |s| *s == 'a'), None);
+    }
+
+    #[test]
+    fn finds_cheapest_paths_with_heuristic() {
+        let grid = get_grid();
+        let goal = Point::new(3, 0);
+        let (path, cost) = astar(
+            Point::new(0, 0),
+            |p| open_neighbours(&grid, *p).into_iter().map(|n| (n, 1)),
+            |p| p.manhattan(goal),
+            |p| *p == goal,
+        )
+        .unwrap();
+        assert_eq!(cost, 7);
+        assert_eq!(path.len(), 8);
+    }
+}
@@ -0,0 +1,85 @@
+/// Known-correct answers, one file per part in `data/answers` (e.g. `data/answers/01-1.txt`).
+/// Answers are recorded when a submission is accepted and can also be added by hand.
+/// Every run checks its results against them, so solutions can be refactored without losing correctness.
+use std::{fmt::Display, fs, io::Error, path::Path};
+
+use crate::template::{paths, Day, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Outcome of checking a result against the known-correct answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verification {
+    Correct,
+    Mismatch(String),
+    Unknown,
+}
+
+impl Verification {
+    pub fn check(expected: Option<&str>, answer: &str) -> Self {
+        match expected {
+            Some(expected) if expected == answer.trim() => Verification::Correct,
+            Some(expected) => Verification::Mismatch(expected.into()),
+            None => Verification::Unknown,
+        }
+    }
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verification::Correct => write!(f, "✓"),
+            Verification::Mismatch(expected) if expected.contains('\n') => {
+                write!(f, "{ANSI_BOLD}✗ mismatch{ANSI_RESET}")
+            }
+            Verification::Mismatch(expected) => {
+                write!(f, "{ANSI_BOLD}✗ expected {expected}{ANSI_RESET}")
+            }
+            Verification::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+/// Read the known-correct answer of a part, if one was recorded.
+pub fn read(year: Option<Year>, day: Day, part: u8) -> Option<String> {
+    let answer = fs::read_to_string(paths::answer_path(year, day, part)).ok()?;
+    let answer = answer.trim();
+    (!answer.is_empty()).then(|| answer.into())
+}
+
+/// Check a result against the known-correct answer of its part.
+pub fn verify(year: Option<Year>, day: Day, part: u8, answer: &str) -> Verification {
+    Verification::check(read(year, day, part).as_deref(), answer)
+}
+
+/// Record the known-correct answer of a part, replacing a previous one.
+pub fn record(year: Option<Year>, day: Day, part: u8, answer: &str) -> Result<(), Error> {
+    let path = paths::answer_path(year, day, part);
+    if let Some(dir) = Path::new(&path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, format!("{}\n", answer.trim()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Verification;
+
+    #[test]
+    fn checks_answers() {
+        assert_eq!(Verification::check(Some("42"), "42"), Verification::Correct);
+        assert_eq!(
+            Verification::check(Some("42"), "41"),
+            Verification::Mismatch("42".into())
+        );
+        assert_eq!(Verification::check(None, "42"), Verification::Unknown);
+    }
+
+    #[test]
+    fn ignores_surrounding_whitespace() {
+        assert_eq!(
+            Verification::check(Some("#..#\n.##."), "#..#\n.##.\n"),
+            Verification::Correct
+        );
+    }
+}
@@ -0,0 +1,504 @@
+/// Built-in HTTP client for adventofcode.com.
+/// Fetches puzzle inputs and descriptions and submits answers without an external `aoc` binary.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::template::{paths, puzzle, Day, Year};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+const USER_AGENT: &str = concat!(
+    "advent_of_code/",
+    env!("CARGO_PKG_VERSION"),
+    " (rust template; ureq)"
+);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    YearNotSet,
+    BadStatus(u16, String),
+    Transport(String),
+    Parser(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::YearNotSet => {
+                write!(f, "`AOC_YEAR` is not set or is not a valid year.")
+            }
+            AocClientError::BadStatus(status, body) => {
+                write!(f, "server responded with status {status}: {body}")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::Parser(e) => write!(f, "unexpected response: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+/// The verdict of the server on a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    WrongLevel,
+    Unknown,
+}
+
+impl SubmissionOutcome {
+    /// Classify the plain text message returned by the server for a submission.
+    pub fn from_message(message: &str) -> Self {
+        if message.contains("That's the right answer") {
+            SubmissionOutcome::Correct
+        } else if message.contains("answer too recently") {
+            SubmissionOutcome::RateLimited
+        } else if message.contains("right level") {
+            SubmissionOutcome::WrongLevel
+        } else if message.contains("not the right answer") {
+            if message.contains("too high") {
+                SubmissionOutcome::TooHigh
+            } else if message.contains("too low") {
+                SubmissionOutcome::TooLow
+            } else {
+                SubmissionOutcome::Incorrect
+            }
+        } else {
+            SubmissionOutcome::Unknown
+        }
+    }
+
+    /// Whether the outcome proves the submitted answer wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            SubmissionOutcome::Incorrect | SubmissionOutcome::TooHigh | SubmissionOutcome::TooLow
+        )
+    }
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SubmissionOutcome::Correct => "correct",
+            SubmissionOutcome::Incorrect => "incorrect",
+            SubmissionOutcome::TooHigh => "too_high",
+            SubmissionOutcome::TooLow => "too_low",
+            SubmissionOutcome::RateLimited => "rate_limited",
+            SubmissionOutcome::WrongLevel => "wrong_level",
+            SubmissionOutcome::Unknown => "unknown",
+        })
+    }
+}
+
+impl FromStr for SubmissionOutcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(SubmissionOutcome::Correct),
+            "incorrect" => Ok(SubmissionOutcome::Incorrect),
+            "too_high" => Ok(SubmissionOutcome::TooHigh),
+            "too_low" => Ok(SubmissionOutcome::TooLow),
+            "rate_limited" => Ok(SubmissionOutcome::RateLimited),
+            "wrong_level" => Ok(SubmissionOutcome::WrongLevel),
+            "unknown" => Ok(SubmissionOutcome::Unknown),
+            s => Err(format!("unknown submission outcome `{s}`.")),
+        }
+    }
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: Year,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+        }
+    }
+
+    /// Configure a client from the environment:
+    ///  1. the session cookie is read from `AOC_SESSION`, falling back to `~/.adventofcode.session`.
+    ///  2. the year is `year` if given, otherwise it is read from `AOC_YEAR`.
+    ///  3. the server can be swapped out via `AOC_BASE_URL`, e.g. for a local stand-in.
+    pub fn from_env(year: Option<Year>) -> Result<Self, AocClientError> {
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        let year = year
+            .or_else(Year::primary)
+            .ok_or(AocClientError::YearNotSet)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session, year))
+    }
+
+    pub fn fetch_input(&self, day: Day) -> Result<String, AocClientError> {
+        let request = self.agent.get(&self.day_url(day, "/input"));
+        self.send(request, None)
+    }
+
+    /// Fetch the puzzle page and return the html of its description `<article>` elements.
+    pub fn fetch_puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let request = self.agent.get(&self.day_url(day, ""));
+        let html = self.send(request, None)?;
+        let articles = extract_articles(&html);
+
+        if articles.is_empty() {
+            return Err(AocClientError::Parser(
+                "puzzle page does not contain a description.".into(),
+            ));
+        }
+
+        Ok(articles.join("\n\n"))
+    }
+
+    /// Submit an answer and return the classified outcome along with the server's message as plain text.
+    pub fn submit(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<(SubmissionOutcome, String), AocClientError> {
+        let request = self.agent.post(&self.day_url(day, "/answer"));
+        let html = self.send(
+            request,
+            Some(&[("level", &part.to_string()), ("answer", answer)]),
+        )?;
+
+        let message = extract_articles(&html)
+            .first()
+            .map(|article| html_to_text(article))
+            .ok_or_else(|| AocClientError::Parser("submission response has no message.".into()))?;
+
+        Ok((SubmissionOutcome::from_message(&message), message))
+    }
+
+    /// Download input and puzzle description for a day to `data/`.
+    pub fn download(&self, day: Day) -> Result<(), AocClientError> {
+        let input_path = paths::input_path(Some(self.year), day);
+        let puzzle_path = paths::puzzle_path(Some(self.year), day);
+
+        write_file(&input_path, &self.fetch_input(day)?)?;
+        write_file(&puzzle_path, &self.fetch_puzzle(day)?)?;
+
+        println!("---");
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(())
+    }
+
+    /// Fetch the puzzle description, store it to `data/puzzles` and print it to the terminal.
+    pub fn read(&self, day: Day) -> Result<(), AocClientError> {
+        let puzzle = self.fetch_puzzle(day)?;
+        write_file(&paths::puzzle_path(Some(self.year), day), &puzzle)?;
+
+        println!(
+            "Fetching puzzle for day {}, {}...",
+            day.into_inner(),
+            self.year
+        );
+        println!();
+        println!("{}", html_to_text(&puzzle));
+        Ok(())
+    }
+
+    fn day_url(&self, day: Day, suffix: &str) -> String {
+        format!(
+            "{}/{}/day/{}{suffix}",
+            self.base_url,
+            self.year,
+            day.into_inner()
+        )
+    }
+
+    fn send(
+        &self,
+        request: ureq::Request,
+        form: Option<&[(&str, &str)]>,
+    ) -> Result<String, AocClientError> {
+        let request = request.set("Cookie", &format!("session={}", self.session));
+
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+
+        match response {
+            Ok(response) => response.into_string().map_err(AocClientError::IO),
+            Err(ureq::Error::Status(status, response)) => Err(AocClientError::BadStatus(
+                status,
+                response
+                    .into_string()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+            )),
+            Err(e) => Err(AocClientError::Transport(e.to_string())),
+        }
+    }
+}
+
+/// Write a file, creating missing parent directories, e.g. for a new year.
+fn write_file(path: &str, contents: &str) -> Result<(), io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let session = fs::read_to_string(PathBuf::from(home).join(SESSION_FILE_NAME)).ok()?;
+    let session = session.trim();
+
+    if session.is_empty() {
+        None
+    } else {
+        Some(session.to_string())
+    }
+}
+
+/// Collect the contents of all `<article>` elements of a page.
+fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(len) = rest[content_start..].find("</article>") else {
+            break;
+        };
+
+        articles.push(rest[content_start..content_start + len].trim());
+        rest = &rest[content_start + len..];
+    }
+
+    articles
+}
+
+/// Render puzzle html as plain text suitable for a terminal.
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let tag = &rest[start + 1..start + end];
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        match name {
+            "li" if !tag.starts_with('/') => text.push_str("  - "),
+            "p" | "pre" | "h2" | "ul" | "li" | "br" => text.push('\n'),
+            _ => (),
+        }
+
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+
+    let text = puzzle::decode_entities(&text);
+
+    let mut lines: Vec<&str> = vec![];
+    for line in text.lines().map(str::trim_end) {
+        if line.is_empty() && lines.last().is_none_or(|l| l.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+
+    lines.join("\n").trim_end().to_string()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    use super::{html_to_text, AocClient, AocClientError, SubmissionOutcome};
+    use crate::{day, year};
+
+    /// Serve a single canned response on a local port and report the raw request back.
+    fn stand_in_server(status: &str, body: &str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            tx.send(request).unwrap();
+        });
+
+        (base_url, rx)
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (base_url, rx) = stand_in_server("200 OK", "1 2 3\n");
+        let client = AocClient::new(&base_url, "abc\n", year!(2024));
+
+        assert_eq!(client.fetch_input(day!(4)).unwrap(), "1 2 3\n");
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2024/day/4/input HTTP/1.1"));
+        assert!(request.contains("session=abc\r\n"));
+    }
+
+    #[test]
+    fn fetches_puzzle_articles() {
+        let page = "<main><article class=\"day-desc\"><h2>--- Day 1 ---</h2><p>Hi</p></article><p>Answer: x</p><article class=\"day-desc\"><p>Part two</p></article></main>";
+        let (base_url, _rx) = stand_in_server("200 OK", page);
+        let client = AocClient::new(&base_url, "abc", year!(2024));
+
+        assert_eq!(
+            client.fetch_puzzle(day!(1)).unwrap(),
+            "<h2>--- Day 1 ---</h2><p>Hi</p>\n\n<p>Part two</p>"
+        );
+    }
+
+    #[test]
+    fn submits_answer_as_form() {
+        let page = "<main><article><p>That's the right answer!</p></article></main>";
+        let (base_url, rx) = stand_in_server("200 OK", page);
+        let client = AocClient::new(&base_url, "abc", year!(2024));
+
+        assert_eq!(
+            client.submit(day!(11), 2, "42").unwrap(),
+            (
+                SubmissionOutcome::Correct,
+                "That's the right answer!".into()
+            )
+        );
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("POST /2024/day/11/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (base_url, _rx) = stand_in_server("400 Bad Request", "Please log in.\n");
+        let client = AocClient::new(&base_url, "abc", year!(2024));
+
+        match client.fetch_input(day!(1)) {
+            Err(AocClientError::BadStatus(400, body)) => assert_eq!(body, "Please log in."),
+            _ => panic!("expected a bad status error"),
+        }
+    }
+
+    #[test]
+    fn classifies_submission_messages() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                SubmissionOutcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                SubmissionOutcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                SubmissionOutcome::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck...",
+                SubmissionOutcome::Incorrect,
+            ),
+            (
+                "You gave an answer too recently; you have 37s left to wait.",
+                SubmissionOutcome::RateLimited,
+            ),
+            (
+                "You don't seem to be solving the right level.",
+                SubmissionOutcome::WrongLevel,
+            ),
+            ("Something else entirely.", SubmissionOutcome::Unknown),
+        ];
+
+        for (message, outcome) in cases {
+            assert_eq!(SubmissionOutcome::from_message(message), outcome);
+        }
+    }
+
+    #[test]
+    fn renders_html_as_text() {
+        let html = "<h2>--- Day 1 ---</h2><p>Find <em>x</em> &amp; y:</p>\n<pre><code>a &lt; b\n</code></pre>\n<ul><li>one</li><li>two</li></ul>";
+        assert_eq!(
+            html_to_text(html),
+            "--- Day 1 ---\n\nFind x & y:\n\na < b\n\n  - one\n  - two"
+        );
+    }
+}
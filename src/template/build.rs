@@ -0,0 +1,189 @@
+/// Builds solution bins up front, so they can be executed directly instead of through `cargo run`.
+/// Cargo's JSON messages tell which executable belongs to which bin and which bins failed to build.
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader},
+    path::PathBuf,
+    process::{Command, Stdio},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// Cargo profile to build solutions with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Profile {
+    Dev,
+    Release,
+    Dhat,
+}
+
+impl Profile {
+    fn args(self) -> &'static [&'static str] {
+        match self {
+            Profile::Dev => &[],
+            Profile::Release => &["--release"],
+            Profile::Dhat => &["--profile", "dhat", "--features", "dhat-heap"],
+        }
+    }
+}
+
+/// Outcome of a build: the executable of every bin that built, and the diagnostics of every target.
+#[derive(Clone, Debug, Default)]
+pub struct BuildSummary {
+    pub executables: HashMap<String, PathBuf>,
+    pub errors: HashMap<String, Vec<String>>,
+    pub warnings: Vec<String>,
+}
+
+impl BuildSummary {
+    /// Path of the built executable of a bin, if it built successfully.
+    pub fn executable(&self, bin_name: &str) -> Option<&PathBuf> {
+        self.executables.get(bin_name)
+    }
+
+    /// Names of all targets that failed to build, sorted.
+    pub fn failed(&self) -> Vec<&str> {
+        let mut failed: Vec<&str> = self.errors.keys().map(String::as_str).collect();
+        failed.sort_unstable();
+        failed
+    }
+
+    /// Print compiler diagnostics, followed by a summary of the targets that failed to build.
+    pub fn print(&self) {
+        for warning in &self.warnings {
+            eprint!("{warning}");
+        }
+
+        let failed = self.failed();
+        if failed.is_empty() {
+            return;
+        }
+
+        for name in &failed {
+            for error in &self.errors[*name] {
+                eprint!("{error}");
+            }
+        }
+
+        eprintln!("{ANSI_BOLD}Build failed{ANSI_RESET}");
+        eprintln!("------------");
+        for name in failed {
+            let count = self.errors[name].len();
+            eprintln!("{name}: {count} error(s)");
+        }
+        eprintln!();
+    }
+
+    /// Collect a summary from the JSON messages of `cargo build --message-format=json`.
+    pub fn parse_messages(lines: impl Iterator<Item = String>) -> Self {
+        let mut summary = BuildSummary::default();
+
+        for line in lines {
+            let Ok(json) = JsonValue::from_str(&line) else {
+                continue;
+            };
+            let Some(message) = json.get::<HashMap<String, JsonValue>>() else {
+                continue;
+            };
+
+            let get_str =
+                |value: Option<&JsonValue>| value.and_then(|v| v.get::<String>()).cloned();
+            let target_name = get_str(
+                message
+                    .get("target")
+                    .and_then(|t| t.get::<HashMap<String, JsonValue>>())
+                    .and_then(|t| t.get("name")),
+            );
+
+            match (get_str(message.get("reason")).as_deref(), target_name) {
+                (Some("compiler-artifact"), Some(name)) => {
+                    if let Some(executable) = get_str(message.get("executable")) {
+                        summary.executables.insert(name, executable.into());
+                    }
+                }
+                (Some("compiler-message"), Some(name)) => {
+                    let diagnostic = message
+                        .get("message")
+                        .and_then(|m| m.get::<HashMap<String, JsonValue>>());
+                    let level = get_str(diagnostic.and_then(|d| d.get("level")));
+                    let Some(rendered) = get_str(diagnostic.and_then(|d| d.get("rendered"))) else {
+                        continue;
+                    };
+
+                    match level.as_deref() {
+                        Some("error") => summary.errors.entry(name).or_default().push(rendered),
+                        Some("warning") => summary.warnings.push(rendered),
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        summary
+    }
+}
+
+/// Build a single bin, or all bins if none is given, in one cargo invocation.
+/// Bins that fail to build do not keep the other bins from building.
+pub fn build(profile: Profile, bin_name: Option<&str>) -> Result<BuildSummary, io::Error> {
+    let mut args = vec![
+        "build",
+        "--quiet",
+        "--keep-going",
+        "--message-format=json-diagnostic-rendered-ansi",
+    ];
+    args.extend(profile.args());
+    match bin_name {
+        Some(bin_name) => args.extend(["--bin", bin_name]),
+        None => args.push("--bins"),
+    }
+
+    let mut cmd = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+
+    let stdout = BufReader::new(cmd.stdout.take().ok_or(io::ErrorKind::BrokenPipe)?);
+    let summary = BuildSummary::parse_messages(stdout.lines().map_while(Result::ok));
+    cmd.wait()?;
+
+    Ok(summary)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::BuildSummary;
+
+    #[test]
+    fn parses_build_messages() {
+        let lines = [
+            r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"advent_of_code"},"executable":null}"#,
+            r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"01"},"executable":"/repo/target/debug/01"}"#,
+            r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"01"},"message":{"level":"warning","rendered":"warning: unused variable\n"}}"#,
+            r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"02"},"message":{"level":"error","rendered":"error: mismatched types\n"}}"#,
+            r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"02"},"message":{"level":"error","rendered":"error: aborting\n"}}"#,
+            "not a json line",
+            r#"{"reason":"build-finished","success":false}"#,
+        ];
+
+        let summary = BuildSummary::parse_messages(lines.into_iter().map(String::from));
+
+        assert_eq!(
+            summary.executable("01"),
+            Some(&PathBuf::from("/repo/target/debug/01"))
+        );
+        assert_eq!(summary.executable("02"), None);
+        assert_eq!(summary.executable("advent_of_code"), None);
+        assert_eq!(summary.failed(), vec!["02"]);
+        assert_eq!(summary.errors["02"].len(), 2);
+        assert_eq!(summary.warnings, vec!["warning: unused variable\n"]);
+    }
+}
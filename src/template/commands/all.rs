@@ -1,5 +1,19 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{
+    all_days, registry,
+    run_multi::{run_multi, run_multi_parallel},
+    Year,
+};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(year: Option<Year>, is_release: bool, is_parallel: bool) {
+    let in_process = registry::solutions().is_some();
+    if is_parallel && in_process {
+        // linked solutions run in-process, one after another.
+        eprintln!("--parallel is not available with the `in-process` feature, running days one after another.");
+    }
+
+    if is_parallel && !in_process {
+        run_multi_parallel(year, &all_days().collect(), is_release);
+    } else {
+        run_multi(year, &all_days().collect(), is_release, false);
+    }
 }
@@ -1,14 +1,17 @@
-use crate::template::{aoc_cli, Day};
+use crate::template::{
+    aoc_client::{AocClient, AocClientError},
+    Day, Year,
+};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+pub fn handle(year: Option<Year>, day: Day) {
+    if let Err(e) = try_download(year, day) {
+        eprintln!("failed to download puzzle: {e}");
         process::exit(1);
     };
 }
+
+/// Download the input and puzzle description of a day, leaving it to the caller to handle errors.
+pub fn try_download(year: Option<Year>, day: Day) -> Result<(), AocClientError> {
+    AocClient::from_env(year)?.download(day)
+}
@@ -1,79 +1,474 @@
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
-};
+use std::{collections::HashMap, fs, path::Path, process};
 
-use crate::template::Day;
+use tinyjson::JsonValue;
 
+use crate::template::{paths, puzzle, Day, Year};
+
+/// The built-in template, used unless `--template` names another one.
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
+/// Directory holding user-defined templates, e.g. `templates/grid.txt` for `--template grid`.
+const TEMPLATES_DIR: &str = "templates";
+
+/// Values for the placeholders of a module template.
+struct TemplateValues {
+    /// `%SOLUTION_ARGS%`: the arguments of the `solution!` macro, e.g. `5` or `5, year = 2023`.
+    solution_args: String,
+    /// `%YEAR_ARG%`: the year argument of the `solution!` macro, e.g. `, year = 2023`, or nothing for the primary year.
+    year_arg: String,
+    /// `%DAY_NUMBER%`: the day without padding, e.g. `5`.
+    day_number: String,
+    /// `%DAY%`: the padded day, e.g. `05`.
+    day: String,
+    /// `%YEAR%`: the year of the puzzle, if known.
+    year: String,
+    /// `%TITLE%`: the puzzle title, e.g. `Day 4: Ceres Search`, or just `Day 4` if the puzzle is not downloaded.
+    title: String,
+    /// `%PART_ONE_ANSWER%` and `%PART_TWO_ANSWER%`: the example answers as expected values,
+    /// e.g. `Some(161)`, or `None` if unknown. Taken from the example manifest or the puzzle description.
+    answers: [String; 2],
+}
+
+impl TemplateValues {
+    fn new(year: Option<Year>, day: Day, puzzle: Option<&str>) -> Self {
+        let year_arg = match year.filter(|year| !year.is_primary()) {
+            Some(year) => format!(", year = {year}"),
+            None => String::new(),
+        };
+
+        let title = puzzle
+            .and_then(puzzle::title)
+            .unwrap_or_else(|| format!("Day {}", day.into_inner()));
+
+        // answers in the example manifest take precedence over those scraped from the puzzle.
+        let puzzle_answers = puzzle.map(puzzle::answers).unwrap_or_default();
+        let answers = [1, 2].map(|part| {
+            let answer = example_answer(year, day, part)
+                .or_else(|| puzzle_answers[usize::from(part) - 1].clone());
+            answer_literal(answer.as_deref())
+        });
+
+        Self {
+            solution_args: format!("{}{year_arg}", day.into_inner()),
+            year_arg,
+            day_number: day.into_inner().to_string(),
+            day: day.to_string(),
+            year: year
+                .or_else(Year::primary)
+                .map(|year| year.to_string())
+                .unwrap_or_default(),
+            title,
+            answers,
+        }
     }
-    file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+fn render(template: &str, values: &TemplateValues) -> String {
+    template
+        .replace("%SOLUTION_ARGS%", &values.solution_args)
+        .replace("%YEAR_ARG%", &values.year_arg)
+        .replace("%DAY_NUMBER%", &values.day_number)
+        .replace("%DAY%", &values.day)
+        .replace("%YEAR%", &values.year)
+        .replace("%TITLE%", &values.title)
+        .replace("%PART_ONE_ANSWER%", &values.answers[0])
+        .replace("%PART_TWO_ANSWER%", &values.answers[1])
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// An expected answer as it is written in a test. Answers that fit the `u32` the templates return
+/// are written as they are, other answers become `None` with the answer in a comment.
+fn answer_literal(answer: Option<&str>) -> String {
+    match answer {
+        Some(answer) if answer.parse::<u32>().is_ok() => format!("Some({answer})"),
+        Some(answer) => {
+            let answer = format!("{answer:?}").replace("*/", "* /");
+            format!("None /* TODO: expected {answer} */")
+        }
+        None => "None".into(),
+    }
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+/// The answer to a part of the first example in the day's manifest that has one.
+fn example_answer(year: Option<Year>, day: Day, part: u8) -> Option<String> {
+    let manifest: JsonValue = fs::read_to_string(paths::example_manifest_path(year, day))
+        .ok()?
+        .parse()
+        .ok()?;
+
+    let examples: &Vec<JsonValue> = manifest.get()?;
+    examples.iter().find_map(|example| {
+        let example: &HashMap<String, JsonValue> = example.get()?;
+        match example.get(&format!("part_{part}"))? {
+            JsonValue::String(answer) => Some(answer.clone()),
+            JsonValue::Number(answer) => Some(format!("{answer:.0}")),
+            _ => None,
         }
+    })
+}
+
+/// Names of the user-defined templates, sorted.
+fn available_templates() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(TEMPLATES_DIR) else {
+        return vec![];
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.strip_suffix(".txt").map(ToString::to_string)
+        })
+        .collect();
+    names.sort_unstable();
+    names
+}
+
+/// The built-in template, or the user-defined template of that name.
+fn load_template(name: Option<&str>) -> Result<String, String> {
+    let Some(name) = name else {
+        return Ok(MODULE_TEMPLATE.into());
+    };
+
+    let path = Path::new(TEMPLATES_DIR).join(format!("{name}.txt"));
+    fs::read_to_string(&path).map_err(|e| {
+        let available = available_templates();
+        if available.is_empty() {
+            format!("could not read \"{}\": {e}", path.display())
+        } else {
+            format!(
+                "could not read \"{}\": {e}. Available templates: {}",
+                path.display(),
+                available.join(", ")
+            )
         }
+    })
+}
+
+/// What scaffolding does with a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    Create,
+    /// Replace the file after backing it up.
+    Overwrite,
+    Skip,
+}
+
+/// A file that scaffolding writes.
+struct ScaffoldFile {
+    /// What the file is, e.g. `input file`.
+    kind: &'static str,
+    path: String,
+    contents: String,
+    /// Whether `--overwrite` may replace existing contents. Inputs are only ever written by `download`.
+    replaceable: bool,
+}
+
+/// Decide what to do with a file. Files with contents are only replaced with `--overwrite`.
+fn plan_action(has_contents: bool, replaceable: bool, overwrite: bool) -> Action {
+    match (has_contents, replaceable && overwrite) {
+        (false, _) => Action::Create,
+        (true, true) => Action::Overwrite,
+        (true, false) => Action::Skip,
     }
+}
+
+/// A path to back up a file to that is not taken yet, e.g. `data/examples/05.txt.bak` or `….bak.2`.
+fn backup_path(path: &str, exists: impl Fn(&str) -> bool) -> String {
+    let backup = format!("{path}.bak");
+    if !exists(&backup) {
+        return backup;
+    }
+
+    (2..)
+        .map(|n| format!("{backup}.{n}"))
+        .find(|backup| !exists(backup))
+        .unwrap()
+}
+
+/// Carry out the action for a file and describe what happened.
+fn apply(file: &ScaffoldFile, action: Action, dry_run: bool) -> Result<String, std::io::Error> {
+    let path = &file.path;
+    let kind = file.kind;
+    let empty = if file.contents.is_empty() {
+        "empty "
+    } else {
+        ""
+    };
+
+    match action {
+        Action::Skip if file.replaceable => Ok(format!(
+            "Skipped {kind} \"{path}\", it already has contents. Use --overwrite to replace it."
+        )),
+        Action::Skip => Ok(format!(
+            "Skipped {kind} \"{path}\", it already has contents."
+        )),
+        Action::Create if dry_run => Ok(format!("Would create {empty}{kind} \"{path}\"")),
+        Action::Create => {
+            create_parent_dir(path)?;
+            fs::write(path, &file.contents)?;
+            Ok(format!("Created {empty}{kind} \"{path}\""))
+        }
+        Action::Overwrite => {
+            let backup = backup_path(path, |p| Path::new(p).exists());
+            if dry_run {
+                return Ok(format!(
+                    "Would overwrite {kind} \"{path}\", backing it up to \"{backup}\""
+                ));
+            }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            fs::copy(path, &backup)?;
+            fs::write(path, &file.contents)?;
+            Ok(format!(
+                "Overwrote {kind} \"{path}\", backed up to \"{backup}\""
+            ))
         }
+    }
+}
+
+/// Whether a file is missing or has no contents besides whitespace.
+/// Files that cannot be read are reported, so that they are never mistaken for blank ones.
+fn is_blank(path: &str) -> Result<bool, std::io::Error> {
+    match fs::read(path) {
+        Ok(contents) => Ok(contents.iter().all(u8::is_ascii_whitespace)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(true),
+        Err(e) => Err(e),
+    }
+}
+
+fn create_parent_dir(path: &str) -> Result<(), std::io::Error> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+/// Adds a `[[bin]]` target for a solution in a year directory to the manifest,
+/// as cargo only discovers binaries at the top level of `src/bin` automatically.
+/// Returns `None` if the target is already registered.
+fn add_bin_target(manifest: &str, name: &str, path: &str) -> Option<String> {
+    let name_line = format!("name = \"{name}\"");
+    if manifest.lines().any(|line| line.trim() == name_line) {
+        return None;
+    }
+
+    let target = format!("[[bin]]\n{name_line}\npath = \"{path}\"\n\n");
+    Some(match manifest.find("[dependencies]") {
+        Some(idx) => format!("{}{target}{}", &manifest[..idx], &manifest[idx..]),
+        None => format!("{manifest}\n{target}"),
+    })
+}
+
+fn register_bin(name: &str, path: &str, dry_run: bool) -> Result<bool, std::io::Error> {
+    let manifest = fs::read_to_string("Cargo.toml")?;
+    match add_bin_target(&manifest, name, path) {
+        Some(_) if dry_run => Ok(true),
+        Some(updated) => fs::write("Cargo.toml", updated).map(|()| true),
+        None => Ok(false),
+    }
+}
+
+/// Create the solution module and data files of a day.
+/// Files that already have contents are skipped, or backed up and replaced with `overwrite`.
+/// With `dry_run`, only the plan is printed.
+pub fn handle(
+    year: Option<Year>,
+    day: Day,
+    overwrite: bool,
+    dry_run: bool,
+    template: Option<&str>,
+) {
+    let module_template = match load_template(template) {
+        Ok(module_template) => module_template,
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+            eprintln!("Failed to load template: {e}");
             process::exit(1);
         }
+    };
+
+    let module_path = paths::bin_path(year, day);
+    let bin_name = paths::bin_name(year, day);
+
+    let puzzle = puzzle::read(year, day);
+    let values = TemplateValues::new(year, day, puzzle.as_deref());
+    let example = puzzle.as_deref().and_then(puzzle::example);
+
+    let files = [
+        ScaffoldFile {
+            kind: "module file",
+            path: module_path.clone(),
+            contents: render(&module_template, &values),
+            replaceable: true,
+        },
+        ScaffoldFile {
+            kind: "input file",
+            path: paths::input_path(year, day),
+            contents: String::new(),
+            replaceable: false,
+        },
+        ScaffoldFile {
+            kind: "example file",
+            path: paths::example_path(year, day),
+            contents: example.unwrap_or_default(),
+            replaceable: true,
+        },
+    ];
+
+    if dry_run {
+        println!("Dry run, no files are changed.");
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    // plan every file before touching any of them.
+    let actions: Vec<Action> = files
+        .iter()
+        .map(|file| match is_blank(&file.path) {
+            Ok(blank) => plan_action(!blank, file.replaceable, overwrite),
+            Err(e) => {
+                eprintln!(
+                    "Failed to read \"{}\", leaving it untouched: {e}",
+                    file.path
+                );
+                process::exit(1);
+            }
+        })
+        .collect();
+
+    let mut module_written = false;
+    for (file, action) in files.iter().zip(actions) {
+        match apply(file, action, dry_run) {
+            Ok(message) => println!("{message}"),
+            Err(e) => {
+                eprintln!("Failed to write \"{}\": {e}", file.path);
+                process::exit(1);
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+        module_written |= file.path == module_path && action != Action::Skip;
+    }
+
+    if bin_name != day.to_string() {
+        match register_bin(&bin_name, &module_path, dry_run) {
+            Ok(true) if dry_run => {
+                println!("Would register binary \"{bin_name}\" in Cargo.toml");
+            }
+            Ok(true) => {
+                println!("Registered binary \"{bin_name}\" in Cargo.toml");
+            }
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Failed to register binary in Cargo.toml: {e}");
+                process::exit(1);
+            }
         }
     }
 
+    if dry_run || !module_written {
+        return;
+    }
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    match year.filter(|year| !year.is_primary()) {
+        Some(year) => println!("🎄 Type `cargo solve {day} --year {year}` to run your solution."),
+        None => println!("🎄 Type `cargo solve {day}` to run your solution."),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        add_bin_target, answer_literal, backup_path, is_blank, plan_action, render, Action,
+        TemplateValues,
+    };
+
+    #[test]
+    fn adds_bin_target_before_dependencies() {
+        let manifest = "[package]\nname = \"aoc\"\n\n[dependencies]\nfoo = \"1\"\n";
+        let updated = add_bin_target(manifest, "2023-05", "src/bin/2023/05.rs").unwrap();
+        assert_eq!(
+            updated,
+            "[package]\nname = \"aoc\"\n\n[[bin]]\nname = \"2023-05\"\npath = \"src/bin/2023/05.rs\"\n\n[dependencies]\nfoo = \"1\"\n"
+        );
+        assert_eq!(
+            add_bin_target(&updated, "2023-05", "src/bin/2023/05.rs"),
+            None
+        );
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let values = TemplateValues {
+            solution_args: "4, year = 2023".into(),
+            year_arg: ", year = 2023".into(),
+            day_number: "4".into(),
+            day: "04".into(),
+            year: "2023".into(),
+            title: "Day 4: Scratchcards".into(),
+            answers: ["Some(13)".into(), "None".into()],
+        };
+
+        assert_eq!(
+            render(
+                "// %TITLE% (%YEAR%)\nadvent_of_code::solution!(%SOLUTION_ARGS%);\nsolution!(%DAY_NUMBER%, 1%YEAR_ARG%);\n%DAY% %PART_ONE_ANSWER% %PART_TWO_ANSWER%",
+                &values
+            ),
+            "// Day 4: Scratchcards (2023)\nadvent_of_code::solution!(4, year = 2023);\nsolution!(4, 1, year = 2023);\n04 Some(13) None"
+        );
+    }
+
+    #[test]
+    fn renders_answers_as_literals() {
+        assert_eq!(answer_literal(Some("161")), "Some(161)");
+        assert_eq!(
+            answer_literal(Some("-3")),
+            "None /* TODO: expected \"-3\" */"
+        );
+        assert_eq!(
+            answer_literal(Some("4,6,3")),
+            "None /* TODO: expected \"4,6,3\" */"
+        );
+        assert_eq!(
+            answer_literal(Some("a*/b")),
+            "None /* TODO: expected \"a* /b\" */"
+        );
+        assert_eq!(answer_literal(None), "None");
+    }
+
+    #[test]
+    fn plans_actions() {
+        assert_eq!(plan_action(false, false, false), Action::Create);
+        assert_eq!(plan_action(false, true, true), Action::Create);
+        assert_eq!(plan_action(true, true, false), Action::Skip);
+        assert_eq!(plan_action(true, true, true), Action::Overwrite);
+        // inputs are never replaced.
+        assert_eq!(plan_action(true, false, true), Action::Skip);
+    }
+
+    #[test]
+    fn finds_free_backup_path() {
+        assert_eq!(backup_path("05.txt", |_| false), "05.txt.bak");
+
+        let taken = ["05.txt.bak", "05.txt.bak.2"];
+        assert_eq!(
+            backup_path("05.txt", |p| taken.contains(&p)),
+            "05.txt.bak.3"
+        );
+    }
+
+    #[test]
+    fn checks_raw_contents_for_blankness() {
+        let path = std::env::temp_dir().join(format!("aoc-scaffold-{}.txt", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        assert!(is_blank(path_str).unwrap());
+
+        std::fs::write(&path, " \n").unwrap();
+        assert!(is_blank(path_str).unwrap());
+
+        // not valid UTF-8, but still contents that must not be clobbered.
+        std::fs::write(&path, b"\xff\xfe abc").unwrap();
+        assert!(!is_blank(path_str).unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(is_blank("src").is_err());
+    }
 }
@@ -1,34 +1,49 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::build::{self, Profile};
+use crate::template::{paths, Day, Year};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
-
-    if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
+pub fn handle(year: Option<Year>, day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+    let profile = if dhat {
+        Profile::Dhat
     } else if release {
-        cmd_args.push("--release".to_string());
-    }
+        Profile::Release
+    } else {
+        Profile::Dev
+    };
+
+    let bin_name = paths::bin_name(year, day);
+
+    let summary = match build::build(profile, Some(&bin_name)) {
+        Ok(summary) => summary,
+        Err(e) => {
+            eprintln!("Failed to build solution: {e}");
+            process::exit(1);
+        }
+    };
 
-    cmd_args.push("--".to_string());
+    summary.print();
+
+    let Some(executable) = summary.executable(&bin_name) else {
+        eprintln!("Could not build solution \"{bin_name}\".");
+        process::exit(1);
+    };
+
+    let mut cmd_args = vec![];
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    let status = Command::new(executable)
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
+        .status()
         .unwrap();
 
-    cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
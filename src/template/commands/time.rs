@@ -1,11 +1,24 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::timing_history::{format_timestamp, TimingHistory};
+use crate::template::timings::{TimingDelta, TimingStats, Timings};
+use crate::template::{all_days, paths, readme_benchmarks, Day, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    year: Option<Year>,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    stats: bool,
+    threshold: f64,
+    fail_on_regression: bool,
+) {
+    // the primary year is stored at the top level, same as an unspecified year.
+    let year = year.filter(|year| !year.is_primary());
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
@@ -21,14 +34,38 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(year, &days_to_run, true, true).unwrap();
+
+    if stats {
+        print_stats(&timings);
+    }
+
+    let deltas = stored_timings.compare(&timings);
+    let regressions = deltas.iter().filter(|d| d.is_regression(threshold)).count();
+
+    if !deltas.is_empty() {
+        print_deltas(&deltas, threshold);
+    }
+
+    if fail_on_regression && regressions > 0 {
+        eprintln!(
+            "\n{regressions} part(s) regressed by more than {threshold}%, not storing benchmarks."
+        );
+        process::exit(1);
+    }
 
     if store {
+        let mut history = TimingHistory::read_from_file(year);
+        history.append(&timings, "release");
+        if let Err(e) = history.store_file(year) {
+            eprintln!("Failed to store timing history: {e}");
+        }
+
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(collect_year_timings(year, merged_timings)) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -38,3 +75,134 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Stored timings of the primary year and of every other year with a data directory,
+/// with the timings of `year` replaced by the freshly merged ones.
+fn collect_year_timings(year: Option<Year>, merged: Timings) -> Vec<(Option<Year>, Timings)> {
+    let mut years: Vec<(Option<Year>, Timings)> = std::iter::once(None)
+        .chain(paths::other_years().into_iter().map(Some))
+        .filter(|y| *y != year)
+        .map(|y| (y, Timings::read_from_file(y)))
+        .collect();
+
+    years.push((year, merged));
+    years.sort_unstable_by_key(|(y, _)| *y);
+    years
+}
+
+/// Print how the timings of a day, or of every day if none is given, evolved over stored runs.
+pub fn handle_history(year: Option<Year>, day: Option<Day>) {
+    let history = TimingHistory::read_from_file(year);
+    let mut need_space = false;
+
+    all_days()
+        .filter(|d| day.is_none_or(|day| day == *d))
+        .for_each(|day| {
+            let runs = history.for_day(day);
+            if runs.is_empty() {
+                return;
+            }
+
+            if need_space {
+                println!();
+            }
+            need_space = true;
+
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("------");
+            println!("| Date (UTC) | Commit | Profile | Part 1 | Part 2 | Total |");
+            println!("| :--- | :--- | :--- | ---: | ---: | ---: |");
+
+            for run in runs {
+                println!(
+                    "| {} | {} | {} | `{}` | `{}` | {:.2}ms |",
+                    format_timestamp(run.timestamp),
+                    run.commit.as_deref().unwrap_or("-"),
+                    run.profile,
+                    run.timing.part_1.as_deref().unwrap_or("-"),
+                    run.timing.part_2.as_deref().unwrap_or("-"),
+                    run.timing.total_nanos / 1_000_000_f64
+                );
+            }
+        });
+
+    if !need_space {
+        println!("No stored timing history. Run `cargo time --store` to record one.");
+    }
+}
+
+fn print_deltas(deltas: &[TimingDelta], threshold: f64) {
+    println!();
+    println!("{ANSI_BOLD}Comparison with stored timings{ANSI_RESET}");
+    println!("------------------------------");
+
+    for delta in deltas {
+        let flag = if delta.is_regression(threshold) {
+            format!(" {ANSI_BOLD}⚠ regression{ANSI_RESET}")
+        } else {
+            String::new()
+        };
+
+        println!(
+            "Day {} {}: {} → {} ({:+.1}%){flag}",
+            delta.day,
+            format_part(delta.part),
+            format_nanos(delta.old_nanos),
+            format_nanos(delta.new_nanos),
+            delta.change_percent()
+        );
+    }
+}
+
+fn print_stats(timings: &Timings) {
+    println!();
+    println!("{ANSI_BOLD}Statistics{ANSI_RESET}");
+    println!("----------");
+    println!("| Day | Part | Min | Median | p95 | Max | Std. dev. | Outliers |");
+    println!("| :---: | :---: | ---: | ---: | ---: | ---: | ---: | ---: |");
+
+    for timing in &timings.data {
+        for (part, stats) in [
+            (0, &timing.parse_stats),
+            (1, &timing.part_1_stats),
+            (2, &timing.part_2_stats),
+        ] {
+            if let Some(stats) = stats {
+                println!("{}", format_stats_row(timing.day, part, stats));
+            }
+        }
+    }
+}
+
+/// Label of a part, e.g. `Part 1` with the prefix `Part `, where part `0` is the parse step.
+fn part_label(part: u8, prefix: &str) -> String {
+    if part == 0 {
+        "Parse".into()
+    } else {
+        format!("{prefix}{part}")
+    }
+}
+
+fn format_part(part: u8) -> String {
+    part_label(part, "Part ")
+}
+
+fn format_stats_row(day: Day, part: u8, stats: &TimingStats) -> String {
+    let part = part_label(part, "");
+
+    format!(
+        "| {day} | {part} | {} | {} | {} | {} | {} | {} / {} |",
+        format_nanos(stats.min),
+        format_nanos(stats.median),
+        format_nanos(stats.p95),
+        format_nanos(stats.max),
+        format_nanos(stats.stddev),
+        stats.outliers,
+        stats.samples
+    )
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
@@ -0,0 +1,211 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::answers::{self, Verification};
+use crate::template::build::{self, Profile};
+use crate::template::report::{read_reports, PartReport, REPORT_FILE_ENV};
+use crate::template::{paths, Day, Year, ANSI_BOLD, ANSI_RESET};
+
+/// How often files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Time to wait after a change, so that editors that write several files trigger a single run.
+const SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// Modification times of all watched files, sorted by path.
+type Snapshot = Vec<(PathBuf, Option<SystemTime>)>;
+
+pub fn handle(year: Option<Year>, day: Day, release: bool) {
+    let profile = if release {
+        Profile::Release
+    } else {
+        Profile::Dev
+    };
+
+    let bin_path = paths::bin_path(year, day);
+    if !Path::new(&bin_path).exists() {
+        eprintln!("Solution \"{bin_path}\" does not exist. Run `cargo scaffold {day}` first.");
+        process::exit(1);
+    }
+
+    let mut snapshot = watched_files(year, day);
+    loop {
+        run(year, day, profile);
+        println!("Watching for changes to day {day}. Press Ctrl-C to stop.");
+
+        snapshot = wait_for_change(year, day, &snapshot);
+        println!("\n{ANSI_BOLD}Change detected, re-running…{ANSI_RESET}\n");
+    }
+}
+
+/// Poll the watched files until one of them changes, then return the new snapshot.
+fn wait_for_change(year: Option<Year>, day: Day, previous: &Snapshot) -> Snapshot {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        if watched_files(year, day) != *previous {
+            thread::sleep(SETTLE_TIME);
+            return watched_files(year, day);
+        }
+    }
+}
+
+/// The solution of a day, the library sources, the manifest and the day's example files.
+fn watched_files(year: Option<Year>, day: Day) -> Snapshot {
+    let mut files = vec![
+        PathBuf::from(paths::bin_path(year, day)),
+        "Cargo.toml".into(),
+    ];
+
+    // library sources, without the other solutions.
+    collect_files(Path::new("src"), &mut files, &|path| {
+        !path.starts_with("src/bin")
+    });
+
+    let examples_dir = Path::new(&paths::data_dir(year)).join("examples");
+    collect_files(&examples_dir, &mut files, &|path| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| is_day_example(name, day))
+    });
+
+    let mut snapshot: Snapshot = files
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect();
+    snapshot.sort();
+    snapshot
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>, include: &dyn Fn(&Path) -> bool) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+        if !include(&path) {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, files, include);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Whether a file in the examples directory belongs to a day, e.g. `05.txt`, `05-2.txt` or `05.json`.
+fn is_day_example(file_name: &str, day: Day) -> bool {
+    file_name
+        .strip_prefix(&day.to_string())
+        .is_some_and(|rest| rest.starts_with(['.', '-']))
+}
+
+/// Run the tests of a day, then its solution against the real input, and print a summary.
+fn run(year: Option<Year>, day: Day, profile: Profile) {
+    let bin_name = paths::bin_name(year, day);
+
+    println!("{ANSI_BOLD}Examples{ANSI_RESET}");
+    println!("--------");
+    let tests_passed = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &bin_name])
+        .status()
+        .is_ok_and(|status| status.success());
+
+    println!();
+    println!("{ANSI_BOLD}Input{ANSI_RESET}");
+    println!("-----");
+    let input = run_input(year, day, &bin_name, profile);
+
+    println!();
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("-------");
+    if tests_passed {
+        println!("Examples: ✓ passed");
+    } else {
+        println!("Examples: {ANSI_BOLD}✗ failed{ANSI_RESET}");
+    }
+
+    match input {
+        Err(message) => println!("Input: {ANSI_BOLD}✗ {message}{ANSI_RESET}"),
+        Ok(reports) => {
+            let parts: Vec<&PartReport> = reports.iter().filter(|r| r.part > 0).collect();
+            if parts.is_empty() {
+                println!("Input: no parts ran");
+            }
+
+            for report in parts {
+                let result = match &report.answer {
+                    Some(answer) => {
+                        let verification = answers::verify(year, day, report.part, answer);
+                        match verification {
+                            Verification::Unknown => "solved".to_string(),
+                            _ => verification.to_string(),
+                        }
+                    }
+                    None => "not solved".to_string(),
+                };
+                println!("Part {}: {result}", report.part);
+            }
+        }
+    }
+    println!();
+}
+
+/// Build the solution and run it against the real input, returning the reports of its parts.
+fn run_input(
+    year: Option<Year>,
+    day: Day,
+    bin_name: &str,
+    profile: Profile,
+) -> Result<Vec<PartReport>, String> {
+    if !Path::new(&paths::input_path(year, day)).exists() {
+        println!("No input file. Run `cargo download {day}` to fetch it.");
+        return Err("no input".into());
+    }
+
+    let summary = build::build(profile, Some(bin_name)).map_err(|e| e.to_string())?;
+    summary.print();
+    let executable = summary.executable(bin_name).ok_or("build failed")?;
+
+    let report_path = env::temp_dir().join(format!("aoc-watch-{}-{bin_name}.jsonl", process::id()));
+    let _ = fs::remove_file(&report_path);
+
+    let status = Command::new(executable)
+        .env(REPORT_FILE_ENV, &report_path)
+        .status()
+        .map_err(|e| e.to_string())?;
+
+    let reports = read_reports(&report_path);
+    let _ = fs::remove_file(&report_path);
+
+    if !status.success() {
+        return Err(format!("solution exited with {status}"));
+    }
+    reports
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::is_day_example;
+    use crate::day;
+
+    #[test]
+    fn matches_example_files_of_a_day() {
+        assert!(is_day_example("05.txt", day!(5)));
+        assert!(is_day_example("05-2.txt", day!(5)));
+        assert!(is_day_example("05.json", day!(5)));
+        assert!(!is_day_example("15.txt", day!(5)));
+        assert!(!is_day_example("050.txt", day!(5)));
+        assert!(!is_day_example(".keep", day!(5)));
+    }
+}
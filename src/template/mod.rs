@@ -1,15 +1,27 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod paths;
+pub mod registry;
 pub mod runner;
 
 pub use day::*;
+pub use output::*;
+pub use year::*;
 
+mod answers;
+mod build;
 mod day;
+mod output;
+mod puzzle;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
+mod submissions;
+mod timing_history;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -36,33 +48,116 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Helper function that reads a text file of a given year to a string.
+/// Without a year, or for the primary year, this is the same as [`read_file`].
+#[must_use]
+pub fn read_year_file(folder: &str, year: Option<Year>, day: Day) -> String {
+    let cwd = env::current_dir().unwrap();
+    let filepath = cwd
+        .join(paths::data_dir(year))
+        .join(folder)
+        .join(format!("{day}.txt"));
+    let f = fs::read_to_string(filepath);
+    f.expect("could not open input file")
+}
+
+/// Creates the constants `DAY` and `YEAR` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The optional `year = YYYY` parameter places the solution in that year, e.g. `solution!(5, year = 2023)`.
+/// The optional `parse = <fn>` parameter runs a parse step once and passes a reference to its output
+/// to both parts, e.g. `solution!(5, parse = parse_input)`. The parse step is timed separately.
+/// Parameters go in this order: `solution!(5, 1, year = 2023, parse = parse_input)`.
+///
+/// Tests are generated for every example listed in the day's manifest `data/examples/NN.json`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, year = $year:expr)? $(, parse = $parse:expr)?) => {
+        $crate::solution!(@impl [$($year)?], $day, [$($parse)?], [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:expr, 1 $(, year = $year:expr)? $(, parse = $parse:expr)?) => {
+        $crate::solution!(@impl [$($year)?], $day, [$($parse)?], [part_one, 1]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr, 2 $(, year = $year:expr)? $(, parse = $parse:expr)?) => {
+        $crate::solution!(@impl [$($year)?], $day, [$($parse)?], [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@year) => {
+        None
+    };
+    (@year $year:expr) => {
+        Some($crate::year!($year))
+    };
+
+    (@run $input:ident, [], $( [$func:expr, $part:expr] )*) => {
+        $( $crate::template::runner::run_part($func, $input, YEAR, DAY, $part); )*
+    };
+    (@run $input:ident, [$parse:expr], $( [$func:expr, $part:expr] )*) => {
+        let parsed = $crate::template::runner::run_parse($parse, $input);
+        $( $crate::template::runner::run_part($func, &parsed, YEAR, DAY, $part); )*
+    };
+
+    (@example $input:ident, $part:ident, [], $( [$func:expr, $p:expr] )*) => {
+        $( if $part == $p {
+            return $crate::template::PartOutput::into_outcome($func($input));
+        } )*
+    };
+    (@example $input:ident, $part:ident, [$parse:expr], $( [$func:expr, $p:expr] )*) => {
+        let parsed = $parse($input);
+        $( if $part == $p {
+            return $crate::template::PartOutput::into_outcome($func(&parsed));
+        } )*
+    };
+
+    (@impl [$($year:expr)?], $day:expr, [$($parse:expr)?], $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The year of the current day. `None` refers to the primary year.
+        #[allow(dead_code)]
+        const YEAR: Option<$crate::template::Year> = $crate::solution!(@year $($year)?);
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        /// Runs all parts of the solution against an input.
+        pub fn run_parts(input: &str) {
+            $crate::solution!(@run input, [$($parse)?], $( [$func, $part] )*);
+        }
+
+        /// Registers the solution for running in-process.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                year: YEAR,
+                day: DAY,
+                run: run_parts,
+            };
+
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = $crate::template::read_year_file("inputs", YEAR, DAY);
+            run_parts(&input);
+        }
+
+        /// Tests generated from the example manifest of the day, see `build.rs`.
+        #[cfg(test)]
+        mod examples {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[allow(dead_code)]
+            fn outcome(input: &str, part: u8) -> $crate::template::Outcome {
+                $crate::solution!(@example input, part, [$($parse)?], $( [$func, $part] )*);
+                panic!("part {part} is not part of this solution.")
+            }
+
+            #[allow(dead_code)]
+            fn check(input: &str, part: u8, expected: &str, example: &str) {
+                outcome(input, part).assert_answer(expected, example, part);
+            }
+
+            include!(concat!(env!("OUT_DIR"), "/examples/", env!("CARGO_BIN_NAME"), ".rs"));
         }
     };
 }
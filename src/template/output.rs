@@ -0,0 +1,188 @@
+/// Return types of solution parts, and how the runner renders them.
+use std::error::Error;
+use std::fmt::Display;
+
+/// What a solution part produced, rendered for printing, reporting and submission.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(String),
+    Unsolved,
+    /// An error message, followed by the messages of its sources.
+    Failed(Vec<String>),
+}
+
+impl Outcome {
+    pub fn answer(&self) -> Option<&str> {
+        match self {
+            Outcome::Answer(answer) => Some(answer),
+            _ => None,
+        }
+    }
+
+    /// Panics unless this is the answer `expected`. Used by the tests generated from example manifests.
+    pub fn assert_answer(&self, expected: &str, example: &str, part: u8) {
+        match self {
+            Outcome::Answer(answer) => assert_eq!(
+                answer, expected,
+                "wrong answer for part {part} of example `{example}`"
+            ),
+            Outcome::Unsolved => {
+                panic!("part {part} of example `{example}` is not solved, expected `{expected}`")
+            }
+            Outcome::Failed(chain) => panic!(
+                "part {part} of example `{example}` failed: {}",
+                chain.join("\ncaused by: ")
+            ),
+        }
+    }
+}
+
+/// A type that solution parts can return.
+///
+/// - `Option<T>`: `None` means the part is not solved yet.
+/// - `Result<T, E>`: an error is printed along with its chain of sources.
+///
+/// The answer `T` can be anything that implements [`Display`]. Answers spanning several lines,
+/// e.g. grids, are printed below the part. Use [`Values`] for answers made of several values.
+pub trait PartOutput {
+    fn into_outcome(self) -> Outcome;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Some(answer) => Outcome::Answer(answer.to_string()),
+            None => Outcome::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> PartOutput for Result<T, E> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Ok(answer) => Outcome::Answer(answer.to_string()),
+            Err(e) => {
+                let e: Box<dyn Error> = e.into();
+                let mut chain = vec![e.to_string()];
+                let mut source = e.source();
+                while let Some(e) = source {
+                    chain.push(e.to_string());
+                    source = e.source();
+                }
+                Outcome::Failed(chain)
+            }
+        }
+    }
+}
+
+/// An answer made of several values, rendered comma-separated (e.g. `4,6,3,5`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Values<T>(pub Vec<T>);
+
+impl<T: Display> Display for Values<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, value) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{value}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> FromIterator<T> for Values<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// An answer spanning several lines, e.g. the rows of a grid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lines<T>(pub Vec<T>);
+
+impl<T: Display> Display for Lines<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, line) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> FromIterator<T> for Lines<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{error::Error, fmt::Display};
+
+    use super::{Lines, Outcome, PartOutput, Values};
+
+    #[derive(Debug)]
+    struct ParseError(std::num::ParseIntError);
+
+    impl Display for ParseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "could not parse input")
+        }
+    }
+
+    impl Error for ParseError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn converts_options() {
+        assert_eq!(Some(42).into_outcome(), Outcome::Answer("42".into()));
+        assert_eq!(None::<u32>.into_outcome(), Outcome::Unsolved);
+    }
+
+    #[test]
+    fn converts_errors_with_sources() {
+        let result: Result<u32, _> = "x".parse::<u32>().map_err(ParseError);
+        assert_eq!(
+            result.into_outcome(),
+            Outcome::Failed(vec![
+                "could not parse input".into(),
+                "invalid digit found in string".into()
+            ])
+        );
+
+        let result: Result<u32, String> = Err("no path found".into());
+        assert_eq!(
+            result.into_outcome(),
+            Outcome::Failed(vec!["no path found".into()])
+        );
+    }
+
+    #[test]
+    fn asserts_answers() {
+        Outcome::Answer("48".into()).assert_answer("48", "small", 2);
+
+        let result = std::panic::catch_unwind(|| Outcome::Unsolved.assert_answer("48", "small", 2));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn renders_values_and_lines() {
+        let values: Values<u8> = [4, 6, 3].into_iter().collect();
+        assert_eq!(Some(values).into_outcome(), Outcome::Answer("4,6,3".into()));
+
+        let lines = Lines(vec!["#..", ".#."]);
+        assert_eq!(
+            Some(lines).into_outcome(),
+            Outcome::Answer("#..\n.#.".into())
+        );
+    }
+}
@@ -0,0 +1,106 @@
+/// Locations of solution and data files.
+/// Files of the primary year (or of an unspecified year) use the top-level layout,
+/// files of other years are nested in a directory named after the year.
+use std::fs;
+
+use crate::template::{Day, Year};
+
+/// The year directory to use, if any.
+fn year_dir(year: Option<Year>) -> Option<Year> {
+    year.filter(|year| !year.is_primary())
+}
+
+/// Name of the cargo binary target of a solution, e.g. `05` or `2023-05`.
+#[must_use]
+pub fn bin_name(year: Option<Year>, day: Day) -> String {
+    match year_dir(year) {
+        Some(year) => format!("{year}-{day}"),
+        None => day.to_string(),
+    }
+}
+
+/// Path of a solution module, e.g. `src/bin/05.rs` or `src/bin/2023/05.rs`.
+#[must_use]
+pub fn bin_path(year: Option<Year>, day: Day) -> String {
+    match year_dir(year) {
+        Some(year) => format!("src/bin/{year}/{day}.rs"),
+        None => format!("src/bin/{day}.rs"),
+    }
+}
+
+/// Directory holding inputs, examples, puzzles and stored results, e.g. `data` or `data/2023`.
+#[must_use]
+pub fn data_dir(year: Option<Year>) -> String {
+    match year_dir(year) {
+        Some(year) => format!("data/{year}"),
+        None => "data".into(),
+    }
+}
+
+#[must_use]
+pub fn input_path(year: Option<Year>, day: Day) -> String {
+    format!("{}/inputs/{day}.txt", data_dir(year))
+}
+
+#[must_use]
+pub fn example_path(year: Option<Year>, day: Day) -> String {
+    format!("{}/examples/{day}.txt", data_dir(year))
+}
+
+/// Manifest listing the examples of a day and their answers, e.g. `data/examples/05.json`.
+#[must_use]
+pub fn example_manifest_path(year: Option<Year>, day: Day) -> String {
+    format!("{}/examples/{day}.json", data_dir(year))
+}
+
+#[must_use]
+pub fn puzzle_path(year: Option<Year>, day: Day) -> String {
+    format!("{}/puzzles/{day}.md", data_dir(year))
+}
+
+#[must_use]
+pub fn answer_path(year: Option<Year>, day: Day, part: u8) -> String {
+    format!("{}/answers/{day}-{part}.txt", data_dir(year))
+}
+
+/// All non-primary years that have a data directory, in ascending order.
+pub fn other_years() -> Vec<Year> {
+    let Ok(entries) = fs::read_dir("data") else {
+        return vec![];
+    };
+
+    let mut years: Vec<Year> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+        .filter(|year: &Year| !year.is_primary())
+        .collect();
+
+    years.sort_unstable();
+    years
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bin_name, bin_path, data_dir, input_path};
+    use crate::{day, year};
+
+    #[test]
+    fn uses_top_level_layout_without_year() {
+        assert_eq!(bin_name(None, day!(5)), "05");
+        assert_eq!(bin_path(None, day!(5)), "src/bin/05.rs");
+        assert_eq!(data_dir(None), "data");
+        assert_eq!(input_path(None, day!(5)), "data/inputs/05.txt");
+    }
+
+    #[test]
+    fn uses_year_directories_for_other_years() {
+        let year = Some(year!(2015));
+        assert_eq!(bin_name(year, day!(5)), "2015-05");
+        assert_eq!(bin_path(year, day!(5)), "src/bin/2015/05.rs");
+        assert_eq!(data_dir(year), "data/2015");
+        assert_eq!(input_path(year, day!(5)), "data/2015/inputs/05.txt");
+    }
+}
@@ -0,0 +1,155 @@
+/// Information scraped from downloaded puzzle descriptions in `data/puzzles`.
+/// Descriptions are stored as the html of their `<article>` elements, one per solved part and the next unsolved one.
+/// Scraping examples and answers is a heuristic that fits most puzzles, its results are only ever used as defaults.
+use std::fs;
+
+use crate::template::{paths, Day, Year};
+
+/// Read the stored description of a day, if it has been downloaded.
+pub fn read(year: Option<Year>, day: Day) -> Option<String> {
+    fs::read_to_string(paths::puzzle_path(year, day))
+        .ok()
+        .filter(|html| !html.trim().is_empty())
+}
+
+/// Title of a puzzle, e.g. `Day 4: Ceres Search`, taken from its first heading.
+pub fn title(html: &str) -> Option<String> {
+    let start = html.find("<h2")?;
+    let start = start + html[start..].find('>')? + 1;
+    let end = start + html[start..].find("</h2>")?;
+
+    let title = decode_entities(&strip_tags(&html[start..end]));
+    let title = title
+        .trim()
+        .trim_start_matches('-')
+        .trim_end_matches('-')
+        .trim();
+
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// The first example input of a puzzle: the first code block after "For example" in its first part.
+pub fn example(html: &str) -> Option<String> {
+    let (part_one, _) = split_parts(html);
+    let start = part_one.find("For example").unwrap_or(0);
+    let rest = &part_one[start..];
+
+    let code_start = rest.find("<pre><code>")? + "<pre><code>".len();
+    let code_end = code_start + rest[code_start..].find("</code></pre>")?;
+
+    let example = decode_entities(&strip_tags(&rest[code_start..code_end]));
+    if example.trim().is_empty() {
+        return None;
+    }
+
+    Some(if example.ends_with('\n') {
+        example
+    } else {
+        example + "\n"
+    })
+}
+
+/// The answers of both parts for the example, the last highlighted value (`<code><em>`) of each part.
+/// Part two is only known once its description has been downloaded, i.e. after solving part one.
+pub fn answers(html: &str) -> [Option<String>; 2] {
+    let (part_one, part_two) = split_parts(html);
+    [Some(part_one), part_two].map(|part| last_highlighted(part?))
+}
+
+/// The description of part one and, if present, of part two.
+fn split_parts(html: &str) -> (&str, Option<&str>) {
+    match html.find("<h2 id=\"part2\"") {
+        Some(idx) => (&html[..idx], Some(&html[idx..])),
+        None => (html, None),
+    }
+}
+
+fn last_highlighted(html: &str) -> Option<String> {
+    let start = html.rfind("<code><em>")? + "<code><em>".len();
+    let end = start + html[start..].find("</em></code>")?;
+    let answer = decode_entities(&strip_tags(&html[start..end]));
+    let answer = answer.trim();
+
+    (!answer.is_empty()).then(|| answer.to_string())
+}
+
+/// Remove all tags from a piece of html, keeping their text.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        match rest[start..].find('>') {
+            Some(end) => rest = &rest[start + end + 1..],
+            None => {
+                rest = &rest[start..];
+                break;
+            }
+        }
+    }
+
+    text.push_str(rest);
+    text
+}
+
+/// Replace the html entities that occur in puzzle descriptions.
+pub fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answers, example, title};
+
+    const PUZZLE: &str = "<h2>--- Day 3: Mull It Over ---</h2>\
+        <p>It seems like the goal of the program is just to <em>multiply some numbers</em>.</p>\
+        <p>For example, consider the following section of corrupted memory:</p>\
+        <pre><code>x<em>mul(2,4)</em>&amp;mul[3,7]!@^do_not_<em>mul(5,5)</em>+mul(32,64]\n</code></pre>\
+        <p>Adding up the result of each instruction produces <code><em>161</em></code>.</p>\
+        \n\n<h2 id=\"part2\">--- Part Two ---</h2>\
+        <pre><code>do()</code></pre>\
+        <p>This time, the sum of the results is <code><em>48</em></code>.</p>";
+
+    #[test]
+    fn extracts_title() {
+        let html =
+            "<article class=\"day-desc\"><h2>--- Day 4: Ceres Search ---</h2><p>...</p></article>";
+        assert_eq!(title(html), Some("Day 4: Ceres Search".into()));
+
+        let html = "<article><h2 id=\"part2\">--- Part Two ---</h2></article>";
+        assert_eq!(title(html), Some("Part Two".into()));
+
+        let html = "<h2>--- Day 7: Bridge <em>Repair</em> &amp; More ---</h2>";
+        assert_eq!(title(html), Some("Day 7: Bridge Repair & More".into()));
+
+        assert_eq!(title("<article><p>no heading</p></article>"), None);
+    }
+
+    #[test]
+    fn extracts_example() {
+        assert_eq!(
+            example(PUZZLE),
+            Some("xmul(2,4)&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]\n".into())
+        );
+        assert_eq!(
+            example("<pre><code>1 2\n3 4</code></pre>"),
+            Some("1 2\n3 4\n".into())
+        );
+        assert_eq!(example("<p>For example, nothing.</p>"), None);
+    }
+
+    #[test]
+    fn extracts_answers() {
+        assert_eq!(answers(PUZZLE), [Some("161".into()), Some("48".into())]);
+
+        let (part_one, _) = PUZZLE.split_once("<h2 id").unwrap();
+        assert_eq!(answers(part_one), [Some("161".into()), None]);
+    }
+}
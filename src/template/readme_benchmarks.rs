@@ -3,7 +3,7 @@
 use std::{fs, io};
 
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{paths, Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -26,8 +26,8 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Option<Year>, day: Day) -> String {
+    format!("./{}", paths::bin_path(year, day))
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -52,21 +52,32 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Rows of a benchmark table. A parse column is only shown if any solution has a parse step.
+fn construct_rows(year: Option<Year>, timings: Timings, total_millis: f64) -> Vec<String> {
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = if has_parse {
+        vec![
+            "| Day | Parse | Part 1 | Part 2 |".into(),
+            "| :---: | :---: | :---: | :---:  |".into(),
+        ]
+    } else {
+        vec![
+            "| Day | Part 1 | Part 2 |".into(),
+            "| :---: | :---: | :---:  |".into(),
+        ]
+    };
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(year, timing.day);
+        let parse = if has_parse {
+            format!(" `{}` |", timing.parse.as_deref().unwrap_or("-"))
+        } else {
+            String::new()
+        };
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{parse} `{}` | `{}` |",
             timing.day.into_inner(),
             path,
             timing.part_1.unwrap_or_else(|| "-".into()),
@@ -76,6 +87,38 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    lines
+}
+
+fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+    let header = format!("{prefix} Benchmarks");
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+    lines.extend(construct_rows(None, timings, total_millis));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+/// Construct one table per year, each with its own heading and total.
+fn construct_year_tables(prefix: &str, years: Vec<(Option<Year>, Timings)>) -> String {
+    let header = format!("{prefix} Benchmarks");
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header];
+
+    for (year, timings) in years {
+        let title = year
+            .or_else(Year::primary)
+            .map_or_else(|| "Current year".into(), |year| year.to_string());
+        let total_millis = timings.total_millis();
+
+        lines.push(String::new());
+        lines.push(format!("{prefix}# {title}"));
+        lines.push(String::new());
+        lines.extend(construct_rows(year, timings, total_millis));
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
@@ -88,19 +131,40 @@ fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+fn update_year_content(s: &mut String, years: Vec<(Option<Year>, Timings)>) -> Result<(), Error> {
+    let positions = locate_table(s)?;
+    let table = construct_year_tables("##", years);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+/// Update the readme table with the timings of every year that has any.
+/// The primary year alone renders as one table, otherwise every year gets its own table.
+pub fn update(years: Vec<(Option<Year>, Timings)>) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+
+    let mut years: Vec<_> = years
+        .into_iter()
+        .filter(|(_, timings)| !timings.data.is_empty())
+        .collect();
+
+    if years.len() > 1 || years.iter().any(|(year, _)| year.is_some()) {
+        update_year_content(&mut readme, years)?;
+    } else {
+        let timings = years.pop().map(|(_, t)| t).unwrap_or_default();
+        let total_millis = timings.total_millis();
+        update_content(&mut readme, timings, total_millis)?;
+    }
+
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, update_year_content, MARKER};
+    use crate::{day, template::timings::Timing, template::timings::Timings, year};
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -109,18 +173,30 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -180,4 +256,52 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_year_benchmarks() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        let mut older = get_mock_timings();
+        older.data.truncate(1);
+        update_year_content(&mut s, vec![(Some(year!(2015)), older)]).unwrap();
+        let expected = [
+            "foo",
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "### 2015",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2015/01.rs) | `10ms` | `20ms` |",
+            "",
+            "**Total: 30000.00ms**",
+            "<!--- benchmarking table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.data.truncate(2);
+        timings.data[0].parse = Some("5ms".into());
+        update_content(&mut s, timings, 100.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `5ms` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` |",
+            "",
+            "**Total: 100.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
 }
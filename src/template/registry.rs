@@ -0,0 +1,27 @@
+/// Solutions linked into the main binary, so `all` and `time` can run every day in one process.
+/// The `solution!` macro defines a [`Solution`] in every solution bin. With the `in-process` feature,
+/// the build script includes all bins as modules of the main binary and collects them.
+use std::sync::OnceLock;
+
+use crate::template::{Day, Year};
+
+/// A day's solution, runnable from outside its own bin.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub year: Option<Year>,
+    pub day: Day,
+    /// Runs all parts of the solution against an input, with the same reporting as the bin.
+    pub run: fn(&str),
+}
+
+static SOLUTIONS: OnceLock<&'static [Solution]> = OnceLock::new();
+
+/// Register the solutions linked into this binary. Only the first registration takes effect.
+pub fn register(solutions: &'static [Solution]) {
+    let _ = SOLUTIONS.set(solutions);
+}
+
+/// The registered solutions, if solutions were linked into this binary.
+pub fn solutions() -> Option<&'static [Solution]> {
+    SOLUTIONS.get().copied()
+}
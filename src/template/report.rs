@@ -0,0 +1,177 @@
+/// Machine-readable records that solution binaries emit for each part.
+/// When `AOC_REPORT_FILE` is set, the runner appends one JSON line per part to that file,
+/// which lets `run_multi` collect results without parsing the human-readable output.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    path::Path,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::timings::TimingStats;
+
+pub const REPORT_FILE_ENV: &str = "AOC_REPORT_FILE";
+
+/// Result and timing statistics for one part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    /// `0` for the parse step, which has no answer.
+    pub part: u8,
+    pub answer: Option<String>,
+    pub formatted: String,
+    pub nanos: f64,
+    pub samples: u128,
+    pub stats: Option<TimingStats>,
+}
+
+impl PartReport {
+    /// Append the record to the file named by `AOC_REPORT_FILE`, if set.
+    pub fn emit(&self) -> Result<(), Error> {
+        let Some(path) = env::var_os(REPORT_FILE_ENV) else {
+            return Ok(());
+        };
+
+        let json = JsonValue::from(self).stringify().map_err(Error::other)?;
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{json}")
+    }
+}
+
+/// Read all records from a report file. A missing file yields no records.
+pub fn read_reports(path: &Path) -> Result<Vec<PartReport>, String> {
+    let Ok(s) = fs::read_to_string(path) else {
+        return Ok(vec![]);
+    };
+
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err("not a valid JSON record."))?;
+            PartReport::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(answer) => JsonValue::String(answer.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "formatted".into(),
+            JsonValue::String(value.formatted.clone()),
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(stats) => JsonValue::from(stats),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .ok_or("Expected report.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let formatted = json
+            .get("formatted")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.formatted to be a string.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected report.nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|samples| *samples as u128)
+            .ok_or("Expected report.samples to be a number.")?;
+
+        let stats = json
+            .get("stats")
+            .filter(|v| !v.is_null())
+            .map(TimingStats::try_from)
+            .transpose()?;
+
+        Ok(PartReport {
+            part,
+            answer: answer.cloned(),
+            formatted: formatted.clone(),
+            nanos,
+            samples,
+            stats,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{str::FromStr, time::Duration};
+    use tinyjson::JsonValue;
+
+    use super::PartReport;
+    use crate::template::timings::TimingStats;
+
+    #[test]
+    fn roundtrips_records() {
+        let report = PartReport {
+            part: 1,
+            answer: Some("@ ( ) ms (2s @ 5 samples)".into()),
+            formatted: "74.1ns".into(),
+            nanos: 74.13,
+            samples: 100,
+            stats: TimingStats::from_samples(&[Duration::from_nanos(70), Duration::from_nanos(80)]),
+        };
+        let line = JsonValue::from(&report).stringify().unwrap();
+        let parsed = PartReport::try_from(&JsonValue::from_str(&line).unwrap()).unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn handles_missing_answers() {
+        let json = r#"{ "part": 2, "answer": null, "formatted": "1.0ms", "nanos": 1000000, "samples": 1 }"#;
+        let parsed = PartReport::try_from(&JsonValue::from_str(json).unwrap()).unwrap();
+        assert_eq!(parsed.part, 2);
+        assert_eq!(parsed.answer, None);
+        assert_eq!(parsed.stats, None);
+    }
+}
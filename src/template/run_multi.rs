@@ -1,13 +1,49 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use rayon::prelude::*;
+
+use crate::template::build::{self, BuildSummary, Profile};
+use crate::template::registry;
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Build all solution bins once. Prints compiler diagnostics and a summary of bins that failed to build.
+fn build_solutions(is_release: bool) -> Option<BuildSummary> {
+    let profile = if is_release {
+        Profile::Release
+    } else {
+        Profile::Dev
+    };
+
+    match build::build(profile, None) {
+        Ok(summary) => {
+            summary.print();
+            Some(summary)
+        }
+        Err(e) => {
+            eprintln!("Failed to build solutions: {e}");
+            None
+        }
+    }
+}
+
+/// Run the solutions of several days one after another.
+/// Solutions run in-process if they are linked into this binary, otherwise their bins are built and executed.
+pub fn run_multi(
+    year: Option<Year>,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+) -> Option<Timings> {
+    let solutions = registry::solutions();
+    let summary = match solutions {
+        Some(_) => BuildSummary::default(),
+        None => build_solutions(is_release)?,
+    };
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
@@ -24,13 +60,19 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let result = match solutions {
+                Some(solutions) => child_commands::run_in_process(solutions, year, day, is_timed),
+                None => child_commands::run_solution(year, day, is_timed, &summary),
+            };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            match result {
+                Ok(Some(reports)) => {
+                    let val = child_commands::parse_exec_time(&reports, day);
+                    timings.push(val);
+                }
+                Ok(None) => println!("Not solved."),
+                Err(Error::NotBuilt) => println!("Build failed."),
+                Err(e) => eprintln!("Failed to run solution: {e:?}"),
             }
         });
 
@@ -46,10 +88,53 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Run the solutions of several days concurrently.
+/// All bins are built once up front, then the solution binaries run in parallel.
+/// Output is buffered per day and printed in day order.
+///
+/// Benchmarks would interfere with each other, so this is not available in timed mode.
+pub fn run_multi_parallel(year: Option<Year>, days_to_run: &HashSet<Day>, is_release: bool) {
+    let Some(summary) = build_solutions(is_release) else {
+        return;
+    };
+
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let outputs: Vec<_> = days
+        .par_iter()
+        .map(|day| child_commands::run_solution_buffered(year, *day, &summary))
+        .collect();
+
+    let mut need_space = false;
+
+    for (day, output) in days.into_iter().zip(outputs) {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        match output {
+            Ok(Some(output)) => {
+                print!("{}", output.stdout);
+                eprint!("{}", output.stderr);
+            }
+            Ok(None) => println!("Not solved."),
+            Err(Error::NotBuilt) => println!("Build failed."),
+            Err(e) => eprintln!("Failed to run solution: {e:?}"),
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    NotBuilt,
+    Panicked,
+    Report(String),
     IO(io::Error),
 }
 
@@ -59,48 +144,99 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their reports.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{
+        build::BuildSummary,
+        paths,
+        registry::Solution,
+        report::{read_reports, PartReport, REPORT_FILE_ENV},
+        runner, Day, Year,
+    };
     use std::{
+        env, fs,
         io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        panic,
+        path::{Path, PathBuf},
+        process::{self, Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    /// Captured output of a solution binary.
+    pub struct BufferedOutput {
+        pub stdout: String,
+        pub stderr: String,
+    }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    fn report_path(bin_name: &str) -> PathBuf {
+        env::temp_dir().join(format!("aoc-report-{}-{bin_name}.jsonl", process::id()))
+    }
 
-        if is_release {
-            args.push("--release");
+    /// Look up the built executable of a day.
+    /// Returns `None` for days that have not been scaffolded yet.
+    fn executable(
+        year: Option<Year>,
+        day: Day,
+        summary: &BuildSummary,
+    ) -> Result<Option<(String, &PathBuf)>, Error> {
+        if !Path::new(&paths::bin_path(year, day)).exists() {
+            return Ok(None);
         }
 
+        let bin_name = paths::bin_name(year, day);
+        let executable = summary.executable(&bin_name).ok_or(Error::NotBuilt)?;
+        Ok(Some((bin_name, executable)))
+    }
+
+    /// Run the built solution binary for a given day and capture its output.
+    /// Returns `None` for days that have not been scaffolded yet.
+    pub fn run_solution_buffered(
+        year: Option<Year>,
+        day: Day,
+        summary: &BuildSummary,
+    ) -> Result<Option<BufferedOutput>, Error> {
+        let Some((_, executable)) = executable(year, day, summary)? else {
+            return Ok(None);
+        };
+
+        let output = Command::new(executable).output()?;
+
+        Ok(Some(BufferedOutput {
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        }))
+    }
+
+    /// Run the built solution binary for a given day.
+    /// Returns `None` for days that have not been scaffolded yet.
+    pub fn run_solution(
+        year: Option<Year>,
+        day: Day,
+        is_timed: bool,
+        summary: &BuildSummary,
+    ) -> Result<Option<Vec<PartReport>>, Error> {
+        let Some((bin_name, executable)) = executable(year, day, summary)? else {
+            return Ok(None);
+        };
+
+        let mut args = vec![];
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        let report_path = report_path(&bin_name);
+        let _ = fs::remove_file(&report_path);
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr, results are collected from the report file.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new(executable)
             .args(&args)
+            .env(REPORT_FILE_ENV, &report_path)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -108,8 +244,6 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
                 eprintln!("{}", line.unwrap());
@@ -117,78 +251,92 @@ pub mod child_commands {
         });
 
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            println!("{}", line?);
         }
 
         thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        let reports = read_reports(&report_path).map_err(super::Error::Report)?;
+        let _ = fs::remove_file(&report_path);
+
+        Ok(Some(reports))
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    /// Run the linked solution for a given day in this process.
+    /// Returns `None` for days without a linked solution.
+    pub fn run_in_process(
+        solutions: &[Solution],
+        year: Option<Year>,
+        day: Day,
+        is_timed: bool,
+    ) -> Result<Option<Vec<PartReport>>, Error> {
+        let bin_name = paths::bin_name(year, day);
+        let Some(solution) = solutions
+            .iter()
+            .find(|s| paths::bin_name(s.year, s.day) == bin_name)
+        else {
+            return Ok(None);
+        };
+
+        let input = fs::read_to_string(paths::input_path(year, day))?;
+
+        let report_path = report_path(&bin_name);
+        let _ = fs::remove_file(&report_path);
+
+        // solutions report the same way as their bins, only from this process.
+        env::set_var(REPORT_FILE_ENV, &report_path);
+        runner::set_timed(is_timed);
+        let result = panic::catch_unwind(|| (solution.run)(&input));
+        runner::set_timed(false);
+        env::remove_var(REPORT_FILE_ENV);
+
+        let reports = read_reports(&report_path).map_err(super::Error::Report);
+        let _ = fs::remove_file(&report_path);
+
+        result.map_err(|_| Error::Panicked)?;
+        Ok(Some(reports?))
+    }
+
+    /// Collect the timings of all solved parts from a day's reports.
+    pub fn parse_exec_time(reports: &[PartReport], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
             part_1: None,
             part_2: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse: None,
+            parse_stats: None,
             total_nanos: 0_f64,
         };
 
-        output
+        reports
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            .filter(|report| report.part == 0 || report.answer.is_some())
+            .for_each(|report| {
+                match report.part {
+                    0 => {
+                        timings.parse = Some(report.formatted.clone());
+                        timings.parse_stats.clone_from(&report.stats);
+                    }
+                    1 => {
+                        timings.part_1 = Some(report.formatted.clone());
+                        timings.part_1_stats.clone_from(&report.stats);
+                    }
+                    2 => {
+                        timings.part_2 = Some(report.formatted.clone());
+                        timings.part_2_stats.clone_from(&report.stats);
+                    }
+                    _ => return,
                 }
 
-                timings.total_nanos += nanos;
+                timings.total_nanos += report.nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -207,15 +355,25 @@ pub mod child_commands {
     mod tests {
         use super::parse_exec_time;
 
-        use crate::day;
+        use crate::{day, template::report::PartReport};
+
+        fn report(part: u8, answer: Option<&str>, formatted: &str, nanos: f64) -> PartReport {
+            PartReport {
+                part,
+                answer: answer.map(Into::into),
+                formatted: formatted.into(),
+                nanos,
+                samples: 100,
+                stats: None,
+            }
+        }
 
         #[test]
         fn parses_execution_times() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(1, Some("0"), "74.13ns", 74.13),
+                    report(2, Some("10"), "74.13ms", 74_130_000_f64),
                 ],
                 day!(1),
             );
@@ -228,9 +386,8 @@ pub mod child_commands {
         fn parses_with_patterns_in_input() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    report(1, Some("@ @ @ ( ) ms"), "2s", 2_000_000_000_f64),
+                    report(2, Some("10s (100ms @ 1 samples)"), "100ms", 100_000_000_f64),
                 ],
                 day!(1),
             );
@@ -243,9 +400,8 @@ pub mod child_commands {
         fn parses_missing_parts() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    report(1, None, "1.0µs", 1000_f64),
+                    report(2, None, "1.0µs", 1000_f64),
                 ],
                 day!(1),
             );
@@ -1,35 +1,115 @@
 /// Encapsulates code that interacts with solution functions.
-use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::answers::{self, Verification};
+use crate::template::aoc_client::{AocClient, AocClientError, SubmissionOutcome};
+use crate::template::report::PartReport;
+use crate::template::submissions::{Submission, Submissions};
+use crate::template::timings::TimingStats;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, Outcome, PartOutput, Year, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Whether solutions running in this process are benched, in addition to the `--time` flag.
+static IS_TIMED: AtomicBool = AtomicBool::new(false);
+
+/// Bench solutions that run in-process, as they can't receive the `--time` flag.
+pub fn set_timed(is_timed: bool) {
+    IS_TIMED.store(is_timed, Ordering::Relaxed);
+}
+
+fn is_timed() -> bool {
+    IS_TIMED.load(Ordering::Relaxed) || env::args().any(|x| x == "--time")
+}
+
+pub fn run_part<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, stats) = run_timed(func, input, PartOutput::into_outcome, |result| {
+        print_result(result, &part_str, "", None);
+    });
+
+    let verification = result
+        .answer()
+        .map(|answer| answers::verify(year, day, part, answer));
+
+    let samples = stats.as_ref().map_or(1, |s| s.samples);
+    print_result(
+        &result,
+        &part_str,
+        &format_duration(&duration, samples),
+        verification.as_ref(),
+    );
+
+    let report = PartReport {
+        part,
+        answer: result.answer().map(ToString::to_string),
+        formatted: format!("{duration:.1?}"),
+        nanos: duration.as_secs_f64() * 1_000_000_000_f64,
+        samples,
+        stats,
+    };
+
+    if let Err(e) = report.emit() {
+        eprintln!("failed to write report: {e}");
+    }
+
+    if let Outcome::Answer(answer) = result {
+        submit_result(answer, year, day, part);
+    }
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/// Run the parse step of a solution, timed and reported like a part. Its output is passed to every part.
+pub fn run_parse<I: Clone, P>(func: impl Fn(I) -> P, input: I) -> P {
+    let (parsed, duration, stats) = run_timed(
+        func,
+        input,
+        |parsed| parsed,
+        |_| {
+            print!("Parse:");
+        },
+    );
+
+    let samples = stats.as_ref().map_or(1, |s| s.samples);
+    print!("\r");
+    println!("Parse:{}", format_duration(&duration, samples));
+
+    let report = PartReport {
+        part: 0,
+        answer: None,
+        formatted: format!("{duration:.1?}"),
+        nanos: duration.as_secs_f64() * 1_000_000_000_f64,
+        samples,
+        stats,
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Err(e) = report.emit() {
+        eprintln!("failed to write report: {e}");
     }
+
+    parsed
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
+///
+/// The result of the first execution is converted with `convert` and passed to `hook` before benching.
+fn run_timed<I: Clone, T, O>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    convert: impl FnOnce(T) -> O,
+    hook: impl Fn(&O),
+) -> (O, Duration, Option<TimingStats>) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -41,18 +121,21 @@ fn run_timed<I: Clone, T>(
     };
     let base_time = timer.elapsed();
 
+    let result = convert(result);
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if is_timed() {
+        let stats = bench(func, input, &base_time);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let mean = Duration::from_nanos(stats.mean as u64);
+        (result, mean, Some(stats))
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, None)
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Bench a solution part. A number of warmup iterations run first and are not part of the measurement.
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> TimingStats {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -60,6 +143,11 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let warmup_iterations = (bench_iterations / 10).max(1);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -71,19 +159,8 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    // NOTE: there is at least 10 samples, so stats are always present.
+    TimingStats::from_samples(&timers).unwrap()
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -94,13 +171,19 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(
+    result: &Outcome,
+    part: &str,
+    duration_str: &str,
+    verification: Option<&Verification>,
+) {
     let is_intermediate_result = duration_str.is_empty();
+    let mark = verification.map_or_else(String::new, |v| format!(" {v}"));
 
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+        Outcome::Answer(result) => {
+            if result.contains('\n') {
+                let str = format!("{part}: ▼{mark}{duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -109,7 +192,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                     println!("{result}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{mark}{duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -118,7 +201,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Outcome::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -126,17 +209,33 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Outcome::Failed(chain) => {
+            let str = format!("{part}: ✖ {ANSI_BOLD}{}{ANSI_RESET}", chain[0]);
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}{duration_str}");
+                for cause in &chain[1..] {
+                    println!("  caused by: {cause}");
+                }
+            }
+        }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+///  2. a session cookie is configured.
+///  3. the answer is not ruled out by previous attempts in the submission ledger.
+///
+/// Accepted answers are recorded as known-correct answers.
+fn submit_result(
+    answer: String,
+    year: Option<Year>,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<SubmissionOutcome, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +258,50 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let client = match AocClient::from_env(year) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up aoc client: {e}");
+            process::exit(1);
+        }
+    };
+
+    // without the ledger, known-wrong answers could be submitted again and its history overwritten.
+    let mut submissions = match Submissions::read_from_file(year) {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Not submitting {answer}, failed to read submission ledger {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Some(rejection) = submissions.check(day, part, &answer) {
+        eprintln!("Not submitting {answer}: {rejection}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let outcome = match client.submit(day, part, &answer) {
+        Ok((outcome, message)) => {
+            println!("{message}");
+            outcome
+        }
+        Err(e) => {
+            eprintln!("failed to submit result: {e}");
+            return Some(Err(e));
+        }
+    };
+
+    if outcome == SubmissionOutcome::Correct {
+        if let Err(e) = answers::record(year, day, part, &answer) {
+            eprintln!("failed to record correct answer: {e}");
+        }
+    }
+
+    submissions.push(Submission::new(day, part, &answer, outcome));
+    if let Err(e) = submissions.store_file(year) {
+        eprintln!("failed to store submission ledger: {e}");
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(Ok(outcome))
 }
@@ -0,0 +1,330 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::SubmissionOutcome;
+use crate::template::{paths, Day, Year};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// A single answer submitted for a part of a day.
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: SubmissionOutcome,
+    pub timestamp: f64,
+}
+
+impl Submission {
+    pub fn new(day: Day, part: u8, answer: &str, outcome: SubmissionOutcome) -> Self {
+        Self {
+            day,
+            part,
+            answer: answer.into(),
+            outcome,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0_f64, |d| d.as_secs_f64().floor()),
+        }
+    }
+}
+
+/// Reason to hold back an answer instead of submitting it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    AlreadyCorrect(String),
+    KnownWrong,
+    TooHigh(String),
+    TooLow(String),
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadyCorrect(answer) => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Rejection::KnownWrong => write!(f, "this answer was already submitted and is wrong."),
+            Rejection::TooHigh(bound) => {
+                write!(f, "too high: {bound} was already rejected as too high.")
+            }
+            Rejection::TooLow(bound) => {
+                write!(f, "too low: {bound} was already rejected as too low.")
+            }
+        }
+    }
+}
+
+/// Ledger of all submission attempts.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions of a year to a JSON file.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let dir = paths::data_dir(year);
+        fs::create_dir_all(&dir)?;
+        let mut file = fs::File::create(format!("{dir}/{SUBMISSIONS_FILE_NAME}"))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions of a year from a JSON file. If not present, returns an empty ledger.
+    /// A ledger that cannot be read is an error, so that it is never replaced by an empty one.
+    pub fn read_from_file(year: Option<Year>) -> Result<Self, String> {
+        Self::read_path(&format!(
+            "{}/{SUBMISSIONS_FILE_NAME}",
+            paths::data_dir(year)
+        ))
+    }
+
+    fn read_path(path: &str) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => Submissions::try_from(s).map_err(|e| format!("\"{path}\": {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(format!("\"{path}\": {e}")),
+        }
+    }
+
+    pub fn push(&mut self, submission: Submission) {
+        self.data.push(submission);
+    }
+
+    /// Check an answer against previous attempts for the same part.
+    /// Returns `Some` if the answer should not be submitted.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Option<Rejection> {
+        let attempts = || {
+            self.data
+                .iter()
+                .filter(move |s| s.day == day && s.part == part)
+        };
+
+        if let Some(correct) = attempts().find(|s| s.outcome == SubmissionOutcome::Correct) {
+            return Some(Rejection::AlreadyCorrect(correct.answer.clone()));
+        }
+
+        if attempts().any(|s| s.answer == answer && s.outcome.is_wrong()) {
+            return Some(Rejection::KnownWrong);
+        }
+
+        let value = answer.parse::<i128>().ok()?;
+        let bound = |outcome| {
+            attempts()
+                .filter(move |s| s.outcome == outcome)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(high) = bound(SubmissionOutcome::TooHigh).min() {
+            if value >= high {
+                return Some(Rejection::TooHigh(high.to_string()));
+            }
+        }
+
+        if let Some(low) = bound(SubmissionOutcome::TooLow).max() {
+            if value <= low {
+                return Some(Rejection::TooLow(low.to_string()));
+            }
+        }
+
+        None
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.to_string()),
+        );
+        map.insert("timestamp".into(), JsonValue::Number(value.timestamp));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .filter(|part| (1..=2).contains(part))
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .and_then(|outcome| SubmissionOutcome::from_str(outcome).ok())
+            .ok_or("Expected submission.outcome to be a known outcome.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            outcome,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Rejection, Submission, Submissions};
+    use crate::{day, template::aoc_client::SubmissionOutcome};
+
+    fn get_mock_submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                Submission::new(day!(1), 1, "500", SubmissionOutcome::TooHigh),
+                Submission::new(day!(1), 1, "100", SubmissionOutcome::TooLow),
+                Submission::new(day!(1), 1, "300", SubmissionOutcome::Incorrect),
+                Submission::new(day!(1), 2, "42", SubmissionOutcome::Correct),
+                Submission::new(day!(2), 1, "7", SubmissionOutcome::RateLimited),
+            ],
+        }
+    }
+
+    #[test]
+    fn rejects_known_wrong_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "300"),
+            Some(Rejection::KnownWrong)
+        );
+    }
+
+    #[test]
+    fn rejects_answers_outside_bounds() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "600"),
+            Some(Rejection::TooHigh("500".into()))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "50"),
+            Some(Rejection::TooLow("100".into()))
+        );
+        assert_eq!(submissions.check(day!(1), 1, "250"), None);
+    }
+
+    #[test]
+    fn rejects_solved_parts() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 2, "43"),
+            Some(Rejection::AlreadyCorrect("42".into()))
+        );
+    }
+
+    #[test]
+    fn allows_resubmitting_rate_limited_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(submissions.check(day!(2), 1, "7"), None);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let submissions = get_mock_submissions();
+        let json = JsonValue::from(submissions).stringify().unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed.data.len(), 5);
+        assert_eq!(parsed.data[0].day, day!(1));
+        assert_eq!(parsed.data[0].answer, "500");
+        assert_eq!(parsed.data[0].outcome, SubmissionOutcome::TooHigh);
+        assert_eq!(parsed.data[3].part, 2);
+    }
+
+    #[test]
+    fn refuses_unreadable_ledgers() {
+        let path =
+            std::env::temp_dir().join(format!("aoc-submissions-{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        assert_eq!(Submissions::read_path(path).unwrap().data.len(), 0);
+
+        std::fs::write(path, "{\"data\": [").unwrap();
+        assert!(Submissions::read_path(path).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
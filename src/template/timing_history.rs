@@ -0,0 +1,284 @@
+/// Keeps every stored benchmark run, so the evolution of a solution's speed can be inspected.
+/// The latest run of each day is mirrored in `timings.json`, which drives the readme table.
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::timings::{Timing, Timings};
+use crate::template::{paths, Day, Year};
+
+static HISTORY_FILE_NAME: &str = "timings_history.json";
+
+/// A single stored benchmark run of a day.
+#[derive(Clone, Debug)]
+pub struct TimingRun {
+    pub timing: Timing,
+    pub timestamp: f64,
+    pub commit: Option<String>,
+    pub profile: String,
+}
+
+/// All stored benchmark runs, oldest first.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct TimingHistory {
+    pub runs: Vec<TimingRun>,
+}
+
+impl TimingHistory {
+    /// Dehydrate history of a year to a JSON file.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let dir = paths::data_dir(year);
+        fs::create_dir_all(&dir)?;
+        let mut file = fs::File::create(format!("{dir}/{HISTORY_FILE_NAME}"))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate history of a year from a JSON file. If not present, returns an empty history.
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        let path = format!("{}/{HISTORY_FILE_NAME}", paths::data_dir(year));
+        let Ok(s) = fs::read_to_string(path) else {
+            return TimingHistory::default();
+        };
+
+        match TimingHistory::try_from(s) {
+            Ok(history) => history,
+            Err(e) => {
+                eprintln!("{e}");
+                TimingHistory::default()
+            }
+        }
+    }
+
+    /// Append a run for every day in `timings`, tagged with the current time, commit and profile.
+    pub fn append(&mut self, timings: &Timings, profile: &str) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0_f64, |d| d.as_secs_f64().floor());
+        let commit = get_commit();
+
+        for timing in &timings.data {
+            self.runs.push(TimingRun {
+                timing: timing.clone(),
+                timestamp,
+                commit: commit.clone(),
+                profile: profile.into(),
+            });
+        }
+    }
+
+    /// All runs of a day, oldest first.
+    pub fn for_day(&self, day: Day) -> Vec<&TimingRun> {
+        self.runs.iter().filter(|r| r.timing.day == day).collect()
+    }
+}
+
+/// Short hash of the checked out commit, suffixed with `-dirty` if the working tree has changes.
+fn get_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|o| !o.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+/// Format a unix timestamp as an UTC date and time, e.g. `2024-12-05 10:31`.
+#[allow(clippy::cast_possible_truncation)]
+pub fn format_timestamp(timestamp: f64) -> String {
+    let secs = timestamp as i64;
+    let (days, secs_of_day) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<TimingHistory> for JsonValue {
+    fn from(value: TimingHistory) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "runs".into(),
+            JsonValue::Array(value.runs.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for TimingHistory {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_runs = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("runs")
+            .ok_or("expected JSON document to have key `runs`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.runs` to be an array.")?;
+
+        Ok(TimingHistory {
+            runs: json_runs
+                .iter()
+                .map(TimingRun::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&TimingRun> for JsonValue {
+    fn from(value: &TimingRun) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("timing".into(), JsonValue::from(&value.timing));
+        map.insert("timestamp".into(), JsonValue::Number(value.timestamp));
+        map.insert(
+            "commit".into(),
+            match &value.commit {
+                Some(commit) => JsonValue::String(commit.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for TimingRun {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing run to be a JSON object.")?;
+
+        let timing = json
+            .get("timing")
+            .ok_or("Expected timing run to have a timing.")
+            .map(Timing::try_from)??;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected run.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected run.commit to be null or string.")?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected run.profile to be a string.")?;
+
+        Ok(TimingRun {
+            timing,
+            timestamp,
+            commit: commit.cloned(),
+            profile: profile.clone(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_timestamp, TimingHistory, TimingRun};
+    use crate::{day, template::timings::Timing};
+
+    fn get_mock_run(day: crate::template::Day, timestamp: f64) -> TimingRun {
+        TimingRun {
+            timing: Timing {
+                day,
+                part_1: Some("10ms".into()),
+                part_2: None,
+                part_1_stats: None,
+                part_2_stats: None,
+                parse: None,
+                parse_stats: None,
+                total_nanos: 1e+7,
+            },
+            timestamp,
+            commit: Some("abc1234".into()),
+            profile: "release".into(),
+        }
+    }
+
+    #[test]
+    fn filters_runs_by_day() {
+        let history = TimingHistory {
+            runs: vec![
+                get_mock_run(day!(1), 1.0),
+                get_mock_run(day!(2), 2.0),
+                get_mock_run(day!(1), 3.0),
+            ],
+        };
+        let runs = history.for_day(day!(1));
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[1].timestamp, 3.0);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let history = TimingHistory {
+            runs: vec![get_mock_run(day!(5), 1_733_394_660.0)],
+        };
+        let json = JsonValue::from(history).stringify().unwrap();
+        let parsed = TimingHistory::try_from(json).unwrap();
+        assert_eq!(parsed.runs.len(), 1);
+        assert_eq!(parsed.runs[0].timing.day, day!(5));
+        assert_eq!(parsed.runs[0].commit, Some("abc1234".into()));
+        assert_eq!(parsed.runs[0].profile, "release");
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0.0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_394_660.0), "2024-12-05 10:31");
+        assert_eq!(format_timestamp(951_782_400.0), "2000-02-29 00:00");
+    }
+}
@@ -1,9 +1,9 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{paths, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,9 +11,113 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_stats: Option<TimingStats>,
+    pub part_2_stats: Option<TimingStats>,
+    /// Time spent in the solution's `parse` function, if it has one.
+    pub parse: Option<String>,
+    pub parse_stats: Option<TimingStats>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// Mean execution time of a part in nanoseconds. Part `0` refers to the parse step.
+    /// Falls back to the formatted duration for timings stored without statistics.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        let (formatted, stats) = match part {
+            0 => (&self.parse, &self.parse_stats),
+            1 => (&self.part_1, &self.part_1_stats),
+            2 => (&self.part_2, &self.part_2_stats),
+            _ => return None,
+        };
+
+        match stats {
+            Some(stats) => Some(stats.mean),
+            None => parse_duration(formatted.as_deref()?),
+        }
+    }
+}
+
+/// Change of a part's execution time between a stored and a fresh benchmark.
+/// Part `0` refers to the parse step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingDelta {
+    pub day: Day,
+    pub part: u8,
+    pub old_nanos: f64,
+    pub new_nanos: f64,
+}
+
+impl TimingDelta {
+    /// Relative change in percent. Positive values mean the part got slower.
+    pub fn change_percent(&self) -> f64 {
+        (self.new_nanos - self.old_nanos) / self.old_nanos * 100_f64
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.change_percent() > threshold_percent
+    }
+}
+
+/// Distribution of benchmark samples for a single part, in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingStats {
+    pub samples: u128,
+    pub mean: f64,
+    pub min: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub max: f64,
+    pub stddev: f64,
+    /// Samples outside of the Tukey fences, i.e. more than 1.5 IQR below the first or above the third quartile.
+    pub outliers: u128,
+}
+
+impl TimingStats {
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+
+        let q1 = percentile(&nanos, 0.25);
+        let q3 = percentile(&nanos, 0.75);
+        let iqr = q3 - q1;
+        let outliers = nanos
+            .iter()
+            .filter(|&&x| x < q1 - 1.5 * iqr || x > q3 + 1.5 * iqr)
+            .count();
+
+        Some(Self {
+            samples: nanos.len() as u128,
+            mean,
+            min: nanos[0],
+            median: percentile(&nanos, 0.5),
+            p95: percentile(&nanos, 0.95),
+            max: nanos[nanos.len() - 1],
+            stddev: variance.sqrt(),
+            outliers: outliers as u128,
+        })
+    }
+}
+
+/// Nearest-rank percentile of a sorted, non-empty slice.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -22,16 +126,18 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings of a year to a JSON file.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let dir = paths::data_dir(year);
+        fs::create_dir_all(&dir)?;
+        let mut file = fs::File::create(format!("{dir}/{TIMINGS_FILE_NAME}"))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate timings of a year from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        let s = fs::read_to_string(format!("{}/{TIMINGS_FILE_NAME}", paths::data_dir(year)))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -58,7 +164,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -67,6 +173,35 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Compare `new` timings against `self`, for every part present in both.
+    pub fn compare(&self, new: &Self) -> Vec<TimingDelta> {
+        let mut deltas = vec![];
+
+        for timing in &new.data {
+            let Some(old) = self.data.iter().find(|t| t.day == timing.day) else {
+                continue;
+            };
+
+            for part in [0, 1, 2] {
+                if let (Some(old_nanos), Some(new_nanos)) =
+                    (old.part_nanos(part), timing.part_nanos(part))
+                {
+                    if old_nanos > 0_f64 {
+                        deltas.push(TimingDelta {
+                            day: timing.day,
+                            part,
+                            old_nanos,
+                            new_nanos,
+                        });
+                    }
+                }
+            }
+        }
+
+        deltas.sort_unstable_by_key(|d| (d.day, d.part));
+        deltas
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -74,6 +209,22 @@ impl Timings {
     }
 }
 
+/// Parse a duration formatted with `{:.1?}` back to nanoseconds.
+fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let (value, factor) = if let Some(v) = s.strip_suffix("ns") {
+        (v, 1_f64)
+    } else if let Some(v) = s.strip_suffix("µs") {
+        (v, 1_000_f64)
+    } else if let Some(v) = s.strip_suffix("ms") {
+        (v, 1_000_000_f64)
+    } else {
+        (s.strip_suffix('s')?, 1_000_000_000_f64)
+    };
+
+    value.trim().parse::<f64>().ok().map(|v| v * factor)
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -140,6 +291,22 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if let Some(stats) = &value.part_1_stats {
+            map.insert("part_1_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.part_2_stats {
+            map.insert("part_2_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::String(parse.clone()));
+        }
+
+        if let Some(stats) = &value.parse_stats {
+            map.insert("parse_stats".into(), JsonValue::from(stats));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -173,10 +340,39 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: statistics are optional to stay compatible with timings stored by older versions.
+        let part_1_stats = json
+            .get("part_1_stats")
+            .map(TimingStats::try_from)
+            .transpose()?;
+
+        let part_2_stats = json
+            .get("part_2_stats")
+            .map(TimingStats::try_from)
+            .transpose()?;
+
+        // NOTE: only solutions with a `parse` function have a parse timing.
+        let parse = json
+            .get("parse")
+            .map(|v| {
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be a string.")
+            })
+            .transpose()?;
+
+        let parse_stats = json
+            .get("parse_stats")
+            .map(TimingStats::try_from)
+            .transpose()?;
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats,
+            part_2_stats,
+            parse: parse.cloned(),
+            parse_stats,
             total_nanos,
         })
     }
@@ -184,6 +380,54 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&TimingStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &TimingStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("stddev".into(), JsonValue::Number(value.stddev));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for TimingStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected timing stats.{key} to be a number."))
+        };
+
+        Ok(TimingStats {
+            samples: number("samples")? as u128,
+            mean: number("mean")?,
+            min: number("min")?,
+            median: number("median")?,
+            p95: number("p95")?,
+            max: number("max")?,
+            stddev: number("stddev")?,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
@@ -197,18 +441,30 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -288,11 +544,15 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -302,11 +562,15 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -316,11 +580,122 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod stats {
+        use std::{str::FromStr, time::Duration};
+
+        use tinyjson::JsonValue;
+
+        use crate::template::timings::TimingStats;
+
+        #[test]
+        fn computes_distribution() {
+            let mut samples: Vec<Duration> = (1..=19).map(Duration::from_nanos).collect();
+            samples.push(Duration::from_nanos(1000));
+
+            let stats = TimingStats::from_samples(&samples).unwrap();
+            assert_eq!(stats.samples, 20);
+            assert_eq!(stats.min, 1.0);
+            assert_eq!(stats.median, 10.0);
+            assert_eq!(stats.p95, 19.0);
+            assert_eq!(stats.max, 1000.0);
+            assert_eq!(stats.mean, 59.5);
+            assert_eq!(stats.outliers, 1);
+            assert!(stats.stddev > 200.0 && stats.stddev < 220.0);
+        }
+
+        #[test]
+        fn handles_empty_samples() {
+            assert_eq!(TimingStats::from_samples(&[]), None);
+        }
+
+        #[test]
+        fn roundtrips_json() {
+            let stats = TimingStats::from_samples(&[
+                Duration::from_nanos(10),
+                Duration::from_nanos(20),
+                Duration::from_nanos(30),
+            ])
+            .unwrap();
+            let json = JsonValue::from(&stats).stringify().unwrap();
+            let parsed = TimingStats::try_from(&JsonValue::from_str(&json).unwrap()).unwrap();
+            assert_eq!(parsed, stats);
+        }
+    }
+
+    mod compare {
+        use crate::{
+            day,
+            template::timings::{Timing, Timings},
+        };
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn computes_deltas_for_shared_parts() {
+            let timings = get_mock_timings();
+            let new = Timings {
+                data: vec![
+                    Timing {
+                        day: day!(2),
+                        part_1: Some("33ms".into()),
+                        part_2: Some("20ms".into()),
+                        part_1_stats: None,
+                        part_2_stats: None,
+                        parse: None,
+                        parse_stats: None,
+                        total_nanos: 5.3e+7,
+                    },
+                    Timing {
+                        day: day!(3),
+                        part_1: Some("1ms".into()),
+                        part_2: None,
+                        part_1_stats: None,
+                        part_2_stats: None,
+                        parse: None,
+                        parse_stats: None,
+                        total_nanos: 1e+6,
+                    },
+                ],
+            };
+
+            let deltas = timings.compare(&new);
+            assert_eq!(deltas.len(), 2);
+            assert_eq!(deltas[0].day, day!(2));
+            assert_eq!(deltas[0].part, 1);
+            assert!((deltas[0].change_percent() - 10.0).abs() < 1e-9);
+            assert!(deltas[0].is_regression(5.0));
+            assert!(!deltas[0].is_regression(15.0));
+            assert!((deltas[1].change_percent() + 50.0).abs() < 1e-9);
+            assert!(!deltas[1].is_regression(0.0));
+        }
+
+        #[test]
+        fn parses_formatted_durations() {
+            let timing = Timing {
+                day: day!(1),
+                part_1: Some("74.1µs".into()),
+                part_2: Some("1.5s".into()),
+                part_1_stats: None,
+                part_2_stats: None,
+                parse: Some("12.0ns".into()),
+                parse_stats: None,
+                total_nanos: 0_f64,
+            };
+            assert!((timing.part_nanos(0).unwrap() - 12_f64).abs() < 1e-6);
+            assert!((timing.part_nanos(1).unwrap() - 74_100_f64).abs() < 1e-6);
+            assert!((timing.part_nanos(2).unwrap() - 1.5e+9).abs() < 1e-6);
         }
     }
 
@@ -340,6 +715,10 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -360,6 +739,10 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -0,0 +1,395 @@
+/// A small virtual machine for puzzles that interpret an instruction stream.
+///
+/// An instruction set implements [`Instruction`], choosing its own machine state, e.g. the stock
+/// [`Cpu`] with registers and memory or just a flag. [`Machine`] owns the program counter, steps
+/// through the program, and supports tracing, loop detection and snapshots for brute-force searches.
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// What the machine does after executing an instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Effect<O> {
+    /// Continue with the next instruction.
+    Next,
+    /// Emit an output and continue with the next instruction.
+    Output(O),
+    /// Move the program counter by an offset.
+    Jump(isize),
+    /// Move the program counter to an absolute address.
+    JumpTo(usize),
+    /// Stop the machine.
+    Halt,
+}
+
+/// A single instruction of an instruction set.
+pub trait Instruction {
+    /// State the instructions operate on, besides the program counter.
+    type State: Clone;
+    /// Values emitted by the program.
+    type Output;
+
+    fn execute(&self, state: &mut Self::State) -> Effect<Self::Output>;
+}
+
+/// Result of executing a single instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step<O> {
+    Continue,
+    Output(O),
+    Halted,
+}
+
+/// How a run ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Exit<O> {
+    /// The program halted, with all of its outputs.
+    Halted(Vec<O>),
+    /// The machine was about to execute an instruction at `pc` in a state it had been in before.
+    Looped { pc: usize, outputs: Vec<O> },
+    /// The run stopped after the given number of steps.
+    StepLimit(Vec<O>),
+}
+
+/// General purpose state with numbered registers and growable memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Cpu {
+    pub registers: Vec<i64>,
+    pub memory: Vec<i64>,
+}
+
+impl Cpu {
+    /// A CPU with `registers` registers set to `0` and empty memory.
+    pub fn new(registers: usize) -> Self {
+        Self {
+            registers: vec![0; registers],
+            memory: vec![],
+        }
+    }
+
+    pub fn register(&self, index: usize) -> i64 {
+        self.registers[index]
+    }
+
+    pub fn set_register(&mut self, index: usize, value: i64) {
+        self.registers[index] = value;
+    }
+
+    /// Value at `address`. Memory that was never written reads as `0`.
+    pub fn load(&self, address: usize) -> i64 {
+        self.memory.get(address).copied().unwrap_or(0)
+    }
+
+    /// Store `value` at `address`, growing memory as needed.
+    pub fn store(&mut self, address: usize, value: i64) {
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, 0);
+        }
+        self.memory[address] = value;
+    }
+}
+
+/// The resumable part of a machine: program counter, state and step count.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Snapshot<S> {
+    pub pc: usize,
+    pub state: S,
+    pub steps: usize,
+}
+
+type Tracer<'a, I> = Box<dyn FnMut(usize, &I, &<I as Instruction>::State) + 'a>;
+
+/// Runs a program of instructions against a state.
+/// The machine halts on [`Effect::Halt`] or when the program counter leaves the program.
+pub struct Machine<'a, I: Instruction> {
+    program: Vec<I>,
+    pc: usize,
+    state: I::State,
+    steps: usize,
+    halted: bool,
+    tracer: Option<Tracer<'a, I>>,
+}
+
+impl<'a, I: Instruction> Machine<'a, I> {
+    pub fn new(program: Vec<I>, state: I::State) -> Self {
+        Self {
+            program,
+            pc: 0,
+            state,
+            steps: 0,
+            halted: false,
+            tracer: None,
+        }
+    }
+
+    /// Call `tracer` with the program counter, instruction and state before every instruction.
+    #[must_use]
+    pub fn with_tracer(mut self, tracer: impl FnMut(usize, &I, &I::State) + 'a) -> Self {
+        self.tracer = Some(Box::new(tracer));
+        self
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn state(&self) -> &I::State {
+        &self.state
+    }
+
+    pub fn state_mut(&mut self) -> &mut I::State {
+        &mut self.state
+    }
+
+    /// Number of instructions executed so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    /// Mutable access to the program, e.g. to patch single instructions.
+    pub fn program_mut(&mut self) -> &mut [I] {
+        &mut self.program
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted || self.pc >= self.program.len()
+    }
+
+    /// Execute the instruction at the program counter.
+    pub fn step(&mut self) -> Step<I::Output> {
+        if self.is_halted() {
+            return Step::Halted;
+        }
+
+        let instruction = &self.program[self.pc];
+        if let Some(tracer) = &mut self.tracer {
+            tracer(self.pc, instruction, &self.state);
+        }
+
+        let effect = instruction.execute(&mut self.state);
+        self.steps += 1;
+
+        match effect {
+            Effect::Next => self.pc += 1,
+            Effect::Output(output) => {
+                self.pc += 1;
+                return Step::Output(output);
+            }
+            Effect::Jump(offset) => match self.pc.checked_add_signed(offset) {
+                Some(pc) => self.pc = pc,
+                None => self.halted = true,
+            },
+            Effect::JumpTo(pc) => self.pc = pc,
+            Effect::Halt => self.halted = true,
+        }
+
+        Step::Continue
+    }
+
+    /// Run until the machine halts and collect all outputs.
+    /// Loops forever if the program does; see [`Machine::run_detect_loop`] and [`Machine::run_limited`].
+    pub fn run(&mut self) -> Vec<I::Output> {
+        let mut outputs = vec![];
+        loop {
+            match self.step() {
+                Step::Continue => {}
+                Step::Output(output) => outputs.push(output),
+                Step::Halted => return outputs,
+            }
+        }
+    }
+
+    /// Run until the machine halts or `max_steps` more instructions have been executed.
+    pub fn run_limited(&mut self, max_steps: usize) -> Exit<I::Output> {
+        let mut outputs = vec![];
+        for _ in 0..max_steps {
+            match self.step() {
+                Step::Continue => {}
+                Step::Output(output) => outputs.push(output),
+                Step::Halted => return Exit::Halted(outputs),
+            }
+        }
+
+        if self.is_halted() {
+            Exit::Halted(outputs)
+        } else {
+            Exit::StepLimit(outputs)
+        }
+    }
+
+    /// Run until the machine halts or returns to a program counter and state it was in before.
+    /// A state that keeps changing, e.g. an accumulator, never repeats; use [`Machine::run_detect_loop_by`] then.
+    pub fn run_detect_loop(&mut self) -> Exit<I::Output>
+    where
+        I::State: Hash + Eq,
+    {
+        self.run_detect_loop_by(|pc, state| (pc, state.clone()))
+    }
+
+    /// Run until the machine halts or `key` returns a value it returned before.
+    /// `key` is called with the program counter and state before every instruction,
+    /// e.g. `|pc, _| pc` stops once any instruction is about to run a second time.
+    pub fn run_detect_loop_by<K: Hash + Eq>(
+        &mut self,
+        mut key: impl FnMut(usize, &I::State) -> K,
+    ) -> Exit<I::Output> {
+        let mut seen = HashSet::new();
+        let mut outputs = vec![];
+
+        while !self.is_halted() {
+            if !seen.insert(key(self.pc, &self.state)) {
+                return Exit::Looped {
+                    pc: self.pc,
+                    outputs,
+                };
+            }
+
+            if let Step::Output(output) = self.step() {
+                outputs.push(output);
+            }
+        }
+
+        Exit::Halted(outputs)
+    }
+
+    pub fn snapshot(&self) -> Snapshot<I::State> {
+        Snapshot {
+            pc: self.pc,
+            state: self.state.clone(),
+            steps: self.steps,
+        }
+    }
+
+    /// Return to a snapshot. The program itself is not part of snapshots.
+    pub fn restore(&mut self, snapshot: Snapshot<I::State>) {
+        self.pc = snapshot.pc;
+        self.state = snapshot.state;
+        self.steps = snapshot.steps;
+        self.halted = false;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Cpu, Effect, Exit, Instruction, Machine};
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Op {
+        Acc(i64),
+        Jmp(isize),
+        Nop(isize),
+        Out,
+    }
+
+    impl Instruction for Op {
+        type State = Cpu;
+        type Output = i64;
+
+        fn execute(&self, cpu: &mut Cpu) -> Effect<i64> {
+            match *self {
+                Op::Acc(n) => cpu.set_register(0, cpu.register(0) + n),
+                Op::Jmp(offset) => return Effect::Jump(offset),
+                Op::Nop(_) => {}
+                Op::Out => return Effect::Output(cpu.register(0)),
+            }
+            Effect::Next
+        }
+    }
+
+    /// Boot code of 2020 day 8.
+    fn boot_code() -> Vec<Op> {
+        use Op::*;
+        vec![
+            Nop(0),
+            Acc(1),
+            Jmp(4),
+            Acc(3),
+            Jmp(-3),
+            Acc(-99),
+            Acc(1),
+            Jmp(-4),
+            Acc(6),
+        ]
+    }
+
+    #[test]
+    fn detects_loops() {
+        let mut machine = Machine::new(boot_code(), Cpu::new(1));
+        assert_eq!(
+            machine.run_detect_loop_by(|pc, _| pc),
+            Exit::Looped {
+                pc: 1,
+                outputs: vec![]
+            }
+        );
+        assert_eq!(machine.state().register(0), 5);
+    }
+
+    #[test]
+    fn patches_program_and_restores_snapshots() {
+        let mut program = boot_code();
+        program.push(Op::Out);
+        let mut machine = Machine::new(program, Cpu::new(1));
+        let start = machine.snapshot();
+
+        let fixed = (0..machine.program().len()).find_map(|i| {
+            let original = machine.program()[i];
+            let patched = match original {
+                Op::Jmp(n) => Op::Nop(n),
+                Op::Nop(n) => Op::Jmp(n),
+                _ => return None,
+            };
+
+            machine.restore(start.clone());
+            machine.program_mut()[i] = patched;
+            let exit = machine.run_detect_loop_by(|pc, _| pc);
+            machine.program_mut()[i] = original;
+
+            match exit {
+                Exit::Halted(outputs) => Some(outputs),
+                _ => None,
+            }
+        });
+
+        assert_eq!(fixed, Some(vec![8]));
+    }
+
+    #[test]
+    fn runs_and_traces() {
+        let mut trace = vec![];
+        let program = vec![Op::Acc(2), Op::Out, Op::Acc(3), Op::Out];
+        let outputs = Machine::new(program, Cpu::new(1))
+            .with_tracer(|pc, _, _| trace.push(pc))
+            .run();
+
+        assert_eq!(outputs, vec![2, 5]);
+        assert_eq!(trace, vec![0, 1, 2, 3]);
+
+        let mut machine = Machine::new(vec![Op::Jmp(0)], Cpu::new(1));
+        assert_eq!(machine.run_limited(10), Exit::StepLimit(vec![]));
+        assert_eq!(machine.steps(), 10);
+
+        let mut machine = Machine::new(vec![Op::Nop(0), Op::Jmp(-1)], Cpu::new(1));
+        assert_eq!(
+            machine.run_detect_loop(),
+            Exit::Looped {
+                pc: 0,
+                outputs: vec![]
+            }
+        );
+    }
+
+    #[test]
+    fn uses_memory() {
+        let mut cpu = Cpu::new(2);
+        cpu.store(3, 7);
+        assert_eq!(cpu.load(3), 7);
+        assert_eq!(cpu.load(100), 0);
+        assert_eq!(cpu.memory.len(), 4);
+    }
+}